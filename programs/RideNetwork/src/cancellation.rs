@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::fee_math::{self, Rounding, MAX_BASIS_POINT};
+use crate::state::{
+    Actor, CancellationCharge, CancellationStage, CancellationTier, Job, Status,
+    MAX_CANCELLATION_TIERS,
+};
+
+pub fn validate_cancellation_tiers(cancellation_tiers: &[CancellationTier]) -> Result<()> {
+    if cancellation_tiers.len() > MAX_CANCELLATION_TIERS {
+        return err!(ErrorCode::InvalidCancellationTiers);
+    }
+    let invalid = cancellation_tiers.iter().any(|tier| match tier.charge {
+        CancellationCharge::FareBasisPoint(basis_point) => basis_point as u64 > MAX_BASIS_POINT,
+        CancellationCharge::FlatCent(_) => false,
+    });
+    if invalid {
+        return err!(ErrorCode::InvalidCancellationTiers);
+    }
+    Ok(())
+}
+
+// When the job entered the stage, if it is in it
+fn stage_start(stage: CancellationStage, job: &Job) -> Option<u64> {
+    match (stage, job.status) {
+        (CancellationStage::AfterAcceptance, Status::JobAccepted) => job.job_start_time,
+        (CancellationStage::AfterArrival, Status::Arrived) => job.arrival_time,
+        (CancellationStage::EtaBreach, Status::JobAccepted) => job.pickup_eta,
+        _ => None,
+    }
+}
+
+// Tier for a cancellation right now, evaluated before the job leaves its status.
+// An ETA breach wins over the other stages, then the longest threshold passed
+pub fn applicable_tier(
+    cancellation_tiers: &[CancellationTier],
+    cancelled_by: Actor,
+    job: &Job,
+    now: u64,
+) -> Option<CancellationTier> {
+    cancellation_tiers
+        .iter()
+        .filter(|tier| tier.cancelled_by == cancelled_by)
+        .filter(|tier| {
+            stage_start(tier.stage, job)
+                .map(|start| now > start.saturating_add(tier.after_sec))
+                .unwrap_or(false)
+        })
+        .max_by_key(|tier| (tier.stage == CancellationStage::EtaBreach, tier.after_sec))
+        .copied()
+}
+
+impl CancellationTier {
+    pub fn fee(&self, fare_cent: u64) -> Result<u64> {
+        match self.charge {
+            CancellationCharge::FlatCent(fee) => Ok(fee),
+            CancellationCharge::FareBasisPoint(basis_point) => {
+                fee_math::apply_basis_point(fare_cent, basis_point as u64, Rounding::Down)
+            }
+        }
+    }
+
+    // A late driver pays whoever cancels, otherwise the canceller pays
+    pub fn charged_party(&self) -> Actor {
+        match self.stage {
+            CancellationStage::EtaBreach => Actor::DriverInfra,
+            _ => self.cancelled_by,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::JobTerms;
+
+    fn tier(
+        stage: CancellationStage,
+        cancelled_by: Actor,
+        after_sec: u64,
+        charge: CancellationCharge,
+    ) -> CancellationTier {
+        CancellationTier {
+            stage,
+            cancelled_by,
+            after_sec,
+            charge,
+        }
+    }
+
+    fn schedule() -> Vec<CancellationTier> {
+        vec![
+            tier(
+                CancellationStage::AfterAcceptance,
+                Actor::CustomerInfra,
+                60,
+                CancellationCharge::FlatCent(300),
+            ),
+            tier(
+                CancellationStage::AfterAcceptance,
+                Actor::CustomerInfra,
+                300,
+                CancellationCharge::FareBasisPoint(2_500),
+            ),
+            tier(
+                CancellationStage::AfterArrival,
+                Actor::CustomerInfra,
+                0,
+                CancellationCharge::FlatCent(500),
+            ),
+            tier(
+                CancellationStage::EtaBreach,
+                Actor::CustomerInfra,
+                120,
+                CancellationCharge::FlatCent(400),
+            ),
+            tier(
+                CancellationStage::AfterAcceptance,
+                Actor::DriverInfra,
+                0,
+                CancellationCharge::FlatCent(200),
+            ),
+        ]
+    }
+
+    fn job(status: Status) -> Job {
+        Job {
+            status,
+            job_count: 0,
+            customer_infra: Pubkey::default(),
+            driver_infra: Pubkey::default(),
+            driver_uuid: "driver".to_owned(),
+            distribution: vec![],
+            settlement_protocol: None,
+            total_fee_cent: 4_000,
+            job_initialized_time: 0,
+            job_start_time: Some(1_000),
+            arrival_time: None,
+            job_end_time: None,
+            encrypted_data: "".to_owned(),
+            encrypted_combined_rand_base64: "".to_owned(),
+            pickup_time: None,
+            status_updated_time: 1_000,
+            service: 0,
+            passenger_types: vec![],
+            passenger_count: 1,
+            driver_rating: None,
+            trip_distance_m: 0,
+            trip_duration_sec: 0,
+            quote_hash: [0; 32],
+            waiting_fee_cent: 0,
+            pickup_eta: Some(1_600),
+            terms: JobTerms::default(),
+            applied_cancellation_tier: None,
+        }
+    }
+
+    #[test]
+    fn longest_passed_threshold_applies() {
+        let tiers = schedule();
+        let accepted = job(Status::JobAccepted);
+
+        assert_eq!(
+            applicable_tier(&tiers, Actor::CustomerInfra, &accepted, 1_060),
+            None
+        );
+        let early = applicable_tier(&tiers, Actor::CustomerInfra, &accepted, 1_061).unwrap();
+        assert_eq!(early.fee(4_000), Ok(300));
+        let late = applicable_tier(&tiers, Actor::CustomerInfra, &accepted, 1_301).unwrap();
+        assert_eq!(late.fee(4_000), Ok(1_000));
+        assert_eq!(late.charged_party(), Actor::CustomerInfra);
+    }
+
+    #[test]
+    fn eta_breach_wins_and_charges_the_driver() {
+        let tiers = schedule();
+        let accepted = job(Status::JobAccepted);
+
+        let breach = applicable_tier(&tiers, Actor::CustomerInfra, &accepted, 1_721).unwrap();
+        assert_eq!(breach.stage, CancellationStage::EtaBreach);
+        assert_eq!(breach.charged_party(), Actor::DriverInfra);
+
+        // No ETA, no breach
+        let mut without_eta = job(Status::JobAccepted);
+        without_eta.pickup_eta = None;
+        let tier = applicable_tier(&tiers, Actor::CustomerInfra, &without_eta, 1_721).unwrap();
+        assert_eq!(tier.stage, CancellationStage::AfterAcceptance);
+    }
+
+    #[test]
+    fn tiers_only_match_their_stage_and_party() {
+        let tiers = schedule();
+        let mut arrived = job(Status::Arrived);
+        arrived.arrival_time = Some(1_500);
+
+        let tier = applicable_tier(&tiers, Actor::CustomerInfra, &arrived, 1_501).unwrap();
+        assert_eq!(tier.stage, CancellationStage::AfterArrival);
+        assert_eq!(
+            applicable_tier(&tiers, Actor::DriverInfra, &arrived, 1_501),
+            None
+        );
+        assert_eq!(
+            applicable_tier(&tiers, Actor::CustomerInfra, &job(Status::Init), 5_000),
+            None
+        );
+    }
+
+    #[test]
+    fn fare_share_above_full_is_rejected() {
+        let mut tiers = schedule();
+        assert!(validate_cancellation_tiers(&tiers).is_ok());
+        tiers[0].charge = CancellationCharge::FareBasisPoint(10_001);
+        assert_eq!(
+            validate_cancellation_tiers(&tiers),
+            err!(ErrorCode::InvalidCancellationTiers)
+        );
+    }
+}
@@ -40,4 +40,130 @@ pub enum ErrorCode {
     IncorrectLifecycleJobAccepted,
     #[msg("Job already initialized")]
     JobAlreadyInitialized,
+    #[msg("Job marked as rejected by driver")]
+    RejectedByDriver,
+    #[msg("Incorrect lifecycle, job status must be marked as Started")]
+    IncorrectLifecycleStarted,
+    #[msg("Job status transition is not allowed")]
+    InvalidStatusTransition,
+    #[msg("Party is not allowed to trigger this status transition")]
+    UnauthorizedStatusTransition,
+    #[msg("Job finalize duration is not over")]
+    FinalizeDurationNotOver,
+    #[msg("Incorrect lifecycle, job status must be marked as Completed")]
+    IncorrectLifecycleCompleted,
+    #[msg("Token account for distribution provider not provided")]
+    MissingDistributionAccount,
+    #[msg("Distribution basis points exceed 10,000")]
+    InvalidDistributionBasisPoint,
+    #[msg("Arithmetic overflow in fee calculation")]
+    MathOverflow,
+    #[msg("Dispute already resolved")]
+    DisputeAlreadyResolved,
+    #[msg("Dispute response window is over")]
+    DisputeResponseWindowOver,
+    #[msg("Dispute response window is not over")]
+    DisputeResponseWindowNotOver,
+    #[msg("Signer is not a party to the dispute")]
+    UnauthorizedDisputeParty,
+    #[msg("Maximum dispute evidence submitted")]
+    TooManyDisputeEvidence,
+    #[msg("Counterparty responded, dispute requires a ruling")]
+    DisputeResponded,
+    #[msg("Infra has open jobs or disputes")]
+    InfraHasOpenJobs,
+    #[msg("Infra deposit withdrawal already pending")]
+    WithdrawalAlreadyPending,
+    #[msg("Infra deposit is insufficient")]
+    InsufficientInfraDeposit,
+    #[msg("No pending infra deposit withdrawal")]
+    NoPendingWithdrawal,
+    #[msg("Infra deposit unbonding period is not over")]
+    UnbondingPeriodNotOver,
+    #[msg("Infra deposit meets the country minimum")]
+    InfraDepositSufficient,
+    #[msg("Infra deposit top up grace period is not over")]
+    DepositGracePeriodNotOver,
+    #[msg("Slash already appealed")]
+    SlashAlreadyAppealed,
+    #[msg("Slash appeal window is over")]
+    SlashAppealWindowOver,
+    #[msg("Slash was not appealed")]
+    SlashNotAppealed,
+    #[msg("Slash already reversed")]
+    SlashAlreadyReversed,
+    #[msg("Infra is not verified by the country authority")]
+    InfraNotVerified,
+    #[msg("Infra is frozen")]
+    InfraFrozen,
+    #[msg("Infra is not frozen")]
+    InfraNotFrozen,
+    #[msg("Infra has no timed suspension")]
+    NoTimedSuspension,
+    #[msg("Infra suspension has not expired")]
+    SuspensionNotExpired,
+    #[msg("Invalid deposit tiers")]
+    InvalidDepositTiers,
+    #[msg("Infra reputation is below the country threshold")]
+    ReputationBelowThreshold,
+    #[msg("Catalog item is already approved")]
+    CatalogItemAlreadyValid,
+    #[msg("Catalog item is not approved")]
+    InvalidCatalogItem,
+    #[msg("Catalog account does not match the requested ID")]
+    InvalidCatalogAccount,
+    #[msg("Duplicate catalog item ID")]
+    DuplicateCatalogItem,
+    #[msg("Catalog item belongs to another country")]
+    CatalogItemWrongCountry,
+    #[msg("Driver does not offer the requested service")]
+    ServiceNotOffered,
+    #[msg("Driver does not accept a requested passenger type")]
+    PassengerTypeNotAccepted,
+    #[msg("Passenger count is zero or exceeds the vehicle seats")]
+    InvalidPassengerCount,
+    #[msg("Driver license or credential has expired")]
+    DriverCredentialExpired,
+    #[msg("Driver has already been rated for this job")]
+    DriverAlreadyRated,
+    #[msg("Driver rating must be between 1 and 5")]
+    InvalidDriverRating,
+    #[msg("Driver is not online")]
+    DriverNotOnline,
+    #[msg("Driver holds an active job")]
+    DriverHasActiveJob,
+    #[msg("Availability cannot be set manually")]
+    InvalidAvailability,
+    #[msg("Driver account is required to release an in-progress job")]
+    DriverAccountRequired,
+    #[msg("Driver is already booked on another job")]
+    DriverAlreadyBooked,
+    #[msg("Driver is reserved by another customer infra")]
+    DriverReserved,
+    #[msg("Reservation hold is zero or exceeds the country maximum")]
+    InvalidReservationHold,
+    #[msg("Customer infra does not hold the driver reservation")]
+    NotReservationHolder,
+    #[msg("Driver heartbeat has not timed out")]
+    DriverNotStale,
+    #[msg("Fare is below the country rate card")]
+    FareBelowRateCard,
+    #[msg("Quote is not signed by the driver infra")]
+    InvalidQuoteSignature,
+    #[msg("Quote has expired")]
+    QuoteExpired,
+    #[msg("Quote nonce already used")]
+    QuoteNonceUsed,
+    #[msg("Customer did not show up")]
+    CustomerNoShow,
+    #[msg("No-show wait has not passed")]
+    NoShowWaitNotPassed,
+    #[msg("Invalid cancellation tiers")]
+    InvalidCancellationTiers,
+    #[msg("Customer infra balance cannot cover the fare top up")]
+    InsufficientFareTopUp,
+    #[msg("Driver reservation is already held, release it before reserving again")]
+    ReservationAlreadyHeld,
+    #[msg("Job finalize duration is over, it can no longer be disputed")]
+    FinalizeDurationOver,
 }
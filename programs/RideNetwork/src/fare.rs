@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::fee_math::{self, Rounding};
+
+pub const METRES_PER_KM: u64 = 1_000;
+pub const SECONDS_PER_MINUTE: u64 = 60;
+
+// Country rate card: base rate plus per started km and per started minute
+pub fn compute_fare(
+    base_rate_cent: u64,
+    km_rate_cent: u64,
+    minute_rate_cent: u64,
+    distance_m: u64,
+    duration_sec: u64,
+) -> Result<u64> {
+    let distance_fee = fee_math::mul_div(distance_m, km_rate_cent, METRES_PER_KM, Rounding::Up)?;
+    let duration_fee = fee_math::mul_div(
+        duration_sec,
+        minute_rate_cent,
+        SECONDS_PER_MINUTE,
+        Rounding::Up,
+    )?;
+    fee_math::checked_add(
+        fee_math::checked_add(base_rate_cent, distance_fee)?,
+        duration_fee,
+    )
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum FareAdjustment {
+    TopUp(u64),
+    Refund(u64),
+    None,
+}
+
+// Difference between the escrowed fare and the fare metered on completion
+pub fn fare_adjustment(escrowed_fare: u64, final_fare: u64) -> FareAdjustment {
+    if final_fare > escrowed_fare {
+        FareAdjustment::TopUp(final_fare - escrowed_fare)
+    } else if final_fare < escrowed_fare {
+        FareAdjustment::Refund(escrowed_fare - final_fare)
+    } else {
+        FareAdjustment::None
+    }
+}
+
+// Waiting is billed per started minute once the free time is over, up to the cap
+pub fn waiting_fee(
+    waited_sec: u64,
+    free_sec: u64,
+    minute_rate_cent: u64,
+    max_fee_cent: u64,
+) -> Result<u64> {
+    let billable_sec = waited_sec.saturating_sub(free_sec);
+    let started_minutes = fee_math::mul_div(billable_sec, 1, SECONDS_PER_MINUTE, Rounding::Up)?;
+    let fee = started_minutes
+        .checked_mul(minute_rate_cent)
+        .ok_or(ErrorCode::MathOverflow)?;
+    Ok(fee.min(max_fee_cent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_km_and_minutes_round_up() {
+        // 4,000 base, 10 per km, 15 per minute
+        assert_eq!(compute_fare(4_000, 10, 15, 5_000, 600), Ok(4_200));
+        assert_eq!(compute_fare(4_000, 10, 15, 5_001, 601), Ok(4_202));
+        assert_eq!(compute_fare(4_000, 10, 15, 0, 0), Ok(4_000));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(
+            compute_fare(u64::MAX, 1, 0, 1_000, 0),
+            err!(ErrorCode::MathOverflow)
+        );
+    }
+
+    #[test]
+    fn final_fare_is_topped_up_or_refunded() {
+        assert_eq!(fare_adjustment(4_200, 4_265), FareAdjustment::TopUp(65));
+        assert_eq!(fare_adjustment(4_200, 4_065), FareAdjustment::Refund(135));
+        assert_eq!(fare_adjustment(4_200, 4_200), FareAdjustment::None);
+    }
+
+    #[test]
+    fn waiting_is_free_then_per_started_minute_up_to_cap() {
+        // 300s free, 30 per minute, capped at 900
+        assert_eq!(waiting_fee(0, 300, 30, 900), Ok(0));
+        assert_eq!(waiting_fee(300, 300, 30, 900), Ok(0));
+        assert_eq!(waiting_fee(301, 300, 30, 900), Ok(30));
+        assert_eq!(waiting_fee(420, 300, 30, 900), Ok(60));
+        assert_eq!(waiting_fee(421, 300, 30, 900), Ok(90));
+        assert_eq!(waiting_fee(86_400, 300, 30, 900), Ok(900));
+    }
+}
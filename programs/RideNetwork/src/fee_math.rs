@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+pub const MAX_BASIS_POINT: u64 = 10_000;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Rounding {
+    Down,
+    Up,
+    // Half rounds up
+    Nearest,
+}
+
+// amount * numerator / denominator, computed in u128
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    if denominator == 0 {
+        return err!(ErrorCode::MathOverflow);
+    }
+    let product = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let denominator = denominator as u128;
+    let quotient = product / denominator;
+    let remainder = product % denominator;
+
+    let round_up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => remainder > 0,
+        Rounding::Nearest => remainder * 2 >= denominator,
+    };
+    let result = if round_up { quotient + 1 } else { quotient };
+
+    u64::try_from(result).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+pub fn apply_basis_point(amount: u64, basis_point: u64, rounding: Rounding) -> Result<u64> {
+    mul_div(amount, basis_point, MAX_BASIS_POINT, rounding)
+}
+
+pub fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
+
+pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b)
+        .ok_or_else(|| error!(ErrorCode::MathOverflow))
+}
+
+// Split amount by basis points. Shares are rounded down, then the dust up to
+// the rounded down total is handed out one unit at a time by largest
+// remainder, ties going to the earlier share.
+pub fn split_by_basis_point(amount: u64, basis_points: &[u16]) -> Result<Vec<u64>> {
+    let total_basis_point: u64 = basis_points.iter().map(|bp| *bp as u64).sum();
+    if total_basis_point > MAX_BASIS_POINT {
+        return err!(ErrorCode::InvalidDistributionBasisPoint);
+    }
+
+    let mut shares = Vec::with_capacity(basis_points.len());
+    let mut remainders = Vec::with_capacity(basis_points.len());
+    for (index, bp) in basis_points.iter().enumerate() {
+        let product = amount as u128 * *bp as u128;
+        shares.push((product / MAX_BASIS_POINT as u128) as u64);
+        remainders.push((product % MAX_BASIS_POINT as u128, index));
+    }
+
+    let allocated: u64 = shares.iter().sum();
+    let target = apply_basis_point(amount, total_basis_point, Rounding::Down)?;
+    let dust = checked_sub(target, allocated)?;
+
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for (_, index) in remainders.iter().take(dust as usize) {
+        shares[*index] += 1;
+    }
+
+    Ok(shares)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_amounts_are_not_zeroed() {
+        assert_eq!(apply_basis_point(1_050, 100, Rounding::Down), Ok(10));
+        assert_eq!(apply_basis_point(1_050, 100, Rounding::Up), Ok(11));
+        assert_eq!(apply_basis_point(1_050, 100, Rounding::Nearest), Ok(11));
+        assert_eq!(apply_basis_point(1_040, 100, Rounding::Nearest), Ok(10));
+    }
+
+    #[test]
+    fn overflow_is_an_error() {
+        assert_eq!(
+            mul_div(u64::MAX, 2, 1, Rounding::Down),
+            err!(ErrorCode::MathOverflow)
+        );
+        assert_eq!(
+            mul_div(1, 1, 0, Rounding::Down),
+            err!(ErrorCode::MathOverflow)
+        );
+        assert_eq!(checked_sub(1, 2), err!(ErrorCode::MathOverflow));
+        assert_eq!(
+            apply_basis_point(u64::MAX, MAX_BASIS_POINT, Rounding::Down),
+            Ok(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn split_hands_out_dust_by_largest_remainder() {
+        // Largest remainder wins the dust
+        assert_eq!(
+            split_by_basis_point(10_001, &[3_333, 3_333, 3_334]),
+            Ok(vec![3_333, 3_333, 3_335])
+        );
+        // Equal remainders, the tie goes to the earlier share
+        assert_eq!(
+            split_by_basis_point(10_001, &[5_000, 5_000]),
+            Ok(vec![5_001, 5_000])
+        );
+        assert_eq!(
+            split_by_basis_point(10, &[3_333, 3_333, 3_334]),
+            Ok(vec![3, 3, 4])
+        );
+        assert_eq!(split_by_basis_point(1, &[5_000, 5_000]), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn split_never_exceeds_amount() {
+        for amount in [0, 1, 7, 999, 1_050, 123_456_789] {
+            let shares = split_by_basis_point(amount, &[8_000, 1_000, 100, 900]).unwrap();
+            assert_eq!(shares.iter().sum::<u64>(), amount);
+
+            let shares = split_by_basis_point(amount, &[8_000, 1_000, 100]).unwrap();
+            assert_eq!(
+                shares.iter().sum::<u64>(),
+                apply_basis_point(amount, 9_100, Rounding::Down).unwrap()
+            );
+        }
+        assert_eq!(
+            split_by_basis_point(1, &[9_000, 1_001]),
+            err!(ErrorCode::InvalidDistributionBasisPoint)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::Coordinates;
+
+// Coordinates are stored as integer micro-degrees
+pub const MICRO_DEGREE: i64 = 1_000_000;
+pub const MAX_LAT_MICRO_DEGREE: i32 = 90_000_000;
+pub const MAX_LONG_MICRO_DEGREE: i32 = 180_000_000;
+// Mean earth radius
+pub const EARTH_RADIUS_M: i128 = 6_371_000;
+
+// Fixed-point math is done in i128 with 12 decimals so every validator
+// computes the same distance
+const SCALE: i128 = 1_000_000_000_000;
+const PI: i128 = 3_141_592_653_590;
+const HALF_PI: i128 = PI / 2;
+
+impl Coordinates {
+    // Both axes must be in range, latitude ±90 and longitude ±180
+    pub fn is_valid(&self) -> bool {
+        (-MAX_LAT_MICRO_DEGREE..=MAX_LAT_MICRO_DEGREE).contains(&self.lat)
+            && (-MAX_LONG_MICRO_DEGREE..=MAX_LONG_MICRO_DEGREE).contains(&self.long)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.is_valid() {
+            return err!(ErrorCode::InvalidCoordinatesValid);
+        }
+        Ok(())
+    }
+}
+
+// Great-circle distance in metres, rounded down
+pub fn haversine_distance_m(from: &Coordinates, to: &Coordinates) -> Result<u64> {
+    from.validate()?;
+    to.validate()?;
+
+    let from_lat = to_radian(from.lat);
+    let to_lat = to_radian(to.lat);
+    let half_delta_lat = (to_lat - from_lat) / 2;
+    let mut delta_long = to_radian(to.long) - to_radian(from.long);
+    // Shortest way round the antimeridian
+    if delta_long > PI {
+        delta_long -= 2 * PI;
+    } else if delta_long < -PI {
+        delta_long += 2 * PI;
+    }
+    let half_delta_long = delta_long / 2;
+
+    let sin_half_lat = sin(half_delta_lat);
+    let sin_half_long = sin(half_delta_long);
+    let h = mul(sin_half_lat, sin_half_lat)
+        + mul(
+            mul(cos(from_lat), cos(to_lat)),
+            mul(sin_half_long, sin_half_long),
+        );
+    // Rounding can push h just outside [0, 1]
+    let h = h.clamp(0, SCALE);
+
+    let central_angle = 2 * asin(sqrt(h));
+    let distance = EARTH_RADIUS_M * central_angle / SCALE;
+
+    u64::try_from(distance).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+fn to_radian(micro_degree: i32) -> i128 {
+    micro_degree as i128 * PI / (180 * MICRO_DEGREE as i128)
+}
+
+fn mul(a: i128, b: i128) -> i128 {
+    a * b / SCALE
+}
+
+fn div(a: i128, b: i128) -> i128 {
+    a * SCALE / b
+}
+
+fn sqrt(a: i128) -> i128 {
+    let value = (a * SCALE) as u128;
+    if value == 0 {
+        return 0;
+    }
+    // Newton's method on integers, converges from above
+    let mut x = value;
+    let mut y = (x + 1) / 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x as i128
+}
+
+// Taylor series, valid for x in [-π, π]
+fn sin(x: i128) -> i128 {
+    let x = if x > HALF_PI {
+        PI - x
+    } else if x < -HALF_PI {
+        -PI - x
+    } else {
+        x
+    };
+    let x_squared = mul(x, x);
+    let mut term = x;
+    let mut sum = x;
+    let mut n = 1;
+    while term != 0 {
+        term = -mul(term, x_squared) / ((2 * n) * (2 * n + 1));
+        sum += term;
+        n += 1;
+    }
+    sum
+}
+
+// Valid for x in [-π/2, π/2], which covers every latitude
+fn cos(x: i128) -> i128 {
+    sin(HALF_PI - x.abs())
+}
+
+// Valid for x >= 0
+fn atan(x: i128) -> i128 {
+    if x > SCALE {
+        return HALF_PI - atan(div(SCALE, x));
+    }
+    // Halve the angle twice so the series converges quickly
+    let mut x = x;
+    for _ in 0..2 {
+        x = div(x, SCALE + sqrt(SCALE + mul(x, x)));
+    }
+    let x_squared = mul(x, x);
+    let mut power = x;
+    let mut sum = x;
+    let mut n = 1;
+    while power != 0 {
+        power = -mul(power, x_squared);
+        sum += power / (2 * n + 1);
+        n += 1;
+    }
+    sum * 4
+}
+
+// Valid for y in [0, 1]
+fn asin(y: i128) -> i128 {
+    let cos_angle = sqrt(SCALE - mul(y, y));
+    if cos_angle == 0 {
+        return HALF_PI;
+    }
+    atan(div(y, cos_angle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(lat: i32, long: i32) -> Coordinates {
+        Coordinates { lat, long }
+    }
+
+    #[test]
+    fn each_axis_is_validated_on_its_own_range() {
+        assert!(point(1_282_720, 103_845_455).is_valid());
+        assert!(point(-90_000_000, 180_000_000).is_valid());
+        // Longitudes beyond ±90 are legal
+        assert!(point(0, 120_000_000).is_valid());
+        // One bad axis is enough to reject
+        assert!(!point(90_000_001, 0).is_valid());
+        assert!(!point(0, -180_000_001).is_valid());
+    }
+
+    #[test]
+    fn distance_matches_reference_values() {
+        // One degree of latitude
+        assert_eq!(
+            haversine_distance_m(&point(0, 0), &point(1_000_000, 0)),
+            Ok(111_194)
+        );
+        // Singapore Raffles Place to Changi Airport, about 18.0 km
+        let distance = haversine_distance_m(
+            &point(1_284_000, 103_851_000),
+            &point(1_364_400, 103_991_500),
+        )
+        .unwrap();
+        assert!((17_995..=17_997).contains(&distance), "{}", distance);
+        // Antipodes are half the circumference
+        assert_eq!(
+            haversine_distance_m(&point(0, 0), &point(0, 180_000_000)),
+            Ok(20_015_086)
+        );
+    }
+
+    #[test]
+    fn distance_is_symmetric_and_wraps_the_antimeridian() {
+        let a = point(1_282_720, 103_845_455);
+        let b = point(1_352_083, 103_819_836);
+        assert_eq!(haversine_distance_m(&a, &b), haversine_distance_m(&b, &a));
+        assert_eq!(haversine_distance_m(&a, &a), Ok(0));
+        assert_eq!(
+            haversine_distance_m(&point(0, 179_900_000), &point(0, -179_900_000)),
+            haversine_distance_m(&point(0, -100_000), &point(0, 100_000))
+        );
+    }
+
+    #[test]
+    fn invalid_points_are_rejected() {
+        assert_eq!(
+            haversine_distance_m(&point(91_000_000, 0), &point(0, 0)),
+            err!(ErrorCode::InvalidCoordinatesValid)
+        );
+    }
+}
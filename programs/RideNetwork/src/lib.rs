@@ -1,5 +1,12 @@
+mod cancellation;
 mod error;
+mod fare;
+mod fee_math;
+mod geo;
+mod lifecycle;
 mod processor;
+mod quote;
+mod reputation;
 mod state;
 mod validator;
 
@@ -7,21 +14,28 @@ use anchor_lang::prelude::*;
 use processor::country_processor::*;
 use processor::customer_infra_processor::*;
 use processor::customer_processor::*;
+use processor::dispute_processor::*;
 use processor::driver_infra_processor::*;
 use processor::driver_processor::*;
 use processor::global_processor::*;
 use processor::passenger_processor::*;
 use processor::service_processor::*;
+use processor::settlement_processor::*;
+use processor::slash_processor::*;
 use processor::vehicle_processor::*;
+use quote::DriverQuote;
 use state::*;
 use validator::country_validator::*;
 use validator::customer_infra_validator::*;
 use validator::customer_validator::*;
+use validator::dispute_validator::*;
 use validator::driver_infra_validator::*;
 use validator::driver_validator::*;
 use validator::global_validator::*;
 use validator::passenger_validator::*;
 use validator::service_validator::*;
+use validator::settlement_validator::*;
+use validator::slash_validator::*;
 use validator::vehicle_validator::*;
 
 declare_id!("AdzUEnPqSShvbkMyEsPuz1AZGaKdb6x9kH3hbbtEvKym");
@@ -91,6 +105,48 @@ pub mod ride_network {
         Ok(())
     }
 
+    // TOP UP DRIVER INFRA DEPOSIT
+    pub fn top_up_driver_infra_deposit(
+        ctx: Context<TopUpDriverInfraDeposit>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        amount: u64,
+    ) -> Result<()> {
+        process_top_up_driver_infra_deposit(ctx, amount)?;
+        Ok(())
+    }
+
+    // REQUEST DRIVER INFRA DEPOSIT WITHDRAWAL
+    pub fn request_driver_infra_withdrawal(
+        ctx: Context<RequestDriverInfraWithdrawal>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        amount: u64,
+    ) -> Result<()> {
+        process_request_driver_infra_withdrawal(ctx, amount)?;
+        Ok(())
+    }
+
+    // COMPLETE DRIVER INFRA DEPOSIT WITHDRAWAL AFTER UNBONDING
+    pub fn complete_driver_infra_withdrawal(
+        ctx: Context<CompleteDriverInfraWithdrawal>,
+        _alpha3_country_code: String,
+        driver_infra_count: u64,
+    ) -> Result<()> {
+        process_complete_driver_infra_withdrawal(ctx, driver_infra_count)?;
+        Ok(())
+    }
+
+    // FREEZE UNDERFUNDED DRIVER INFRA, PERMISSIONLESS
+    pub fn freeze_underfunded_driver_infra(
+        ctx: Context<FreezeUnderfundedDriverInfra>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+    ) -> Result<()> {
+        process_freeze_underfunded_driver_infra(ctx)?;
+        Ok(())
+    }
+
     // CREATE OR UPDATE SERVICE
     pub fn init_or_update_service(
         ctx: Context<InitOrUpdateService>,
@@ -123,6 +179,50 @@ pub mod ride_network {
         Ok(())
     }
 
+    // APPROVE OR REJECT SERVICE
+    pub fn approve_service(
+        ctx: Context<ServiceVerification>,
+        _alpha3_country_code: String,
+        service_count: u64,
+    ) -> Result<()> {
+        process_approve_service(ctx, service_count)?;
+        Ok(())
+    }
+    pub fn reject_service(
+        ctx: Context<ServiceVerification>,
+        _alpha3_country_code: String,
+        service_count: u64,
+    ) -> Result<()> {
+        process_reject_service(ctx, service_count)?;
+        Ok(())
+    }
+
+    // APPROVE OR REJECT PASSENGER TYPE
+    pub fn approve_passenger_type(
+        ctx: Context<PassengerTypeVerification>,
+        passenger_type_count: u64,
+    ) -> Result<()> {
+        process_approve_passenger_type(ctx, passenger_type_count)?;
+        Ok(())
+    }
+    pub fn reject_passenger_type(
+        ctx: Context<PassengerTypeVerification>,
+        passenger_type_count: u64,
+    ) -> Result<()> {
+        process_reject_passenger_type(ctx, passenger_type_count)?;
+        Ok(())
+    }
+
+    // APPROVE OR REJECT VEHICLE
+    pub fn approve_vehicle(ctx: Context<VehicleVerification>, vehicle_count: u64) -> Result<()> {
+        process_approve_vehicle(ctx, vehicle_count)?;
+        Ok(())
+    }
+    pub fn reject_vehicle(ctx: Context<VehicleVerification>, vehicle_count: u64) -> Result<()> {
+        process_reject_vehicle(ctx, vehicle_count)?;
+        Ok(())
+    }
+
     // Verify driver and CUSTOMER infra
     pub fn approve_driver_infra(
         ctx: Context<ApproveDriverInfra>,
@@ -144,34 +244,152 @@ pub mod ride_network {
         ctx: Context<DriverInfraSuspension>,
         _alpha3_country_code: String,
         _driver_count: u64,
+        reason: SuspensionReason,
+        duration_sec: Option<u64>,
+    ) -> Result<()> {
+        process_driver_infra_suspension(ctx, reason, duration_sec)?;
+        Ok(())
+    }
+    pub fn reinstate_driver_infra(
+        ctx: Context<ReinstateDriverInfra>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+    ) -> Result<()> {
+        process_reinstate_driver_infra(ctx)?;
+        Ok(())
+    }
+    pub fn lift_expired_driver_infra_suspension(
+        ctx: Context<LiftExpiredDriverInfraSuspension>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
     ) -> Result<()> {
-        process_driver_infra_suspension(ctx)?;
+        process_lift_expired_driver_infra_suspension(ctx)?;
         Ok(())
     }
     pub fn customer_infra_suspension(
         ctx: Context<CustomerInfraSuspension>,
         _alpha3_country_code: String,
         _customer_count: u64,
+        reason: SuspensionReason,
+        duration_sec: Option<u64>,
     ) -> Result<()> {
-        process_customer_infra_suspension(ctx)?;
+        process_customer_infra_suspension(ctx, reason, duration_sec)?;
+        Ok(())
+    }
+    pub fn reinstate_customer_infra(
+        ctx: Context<ReinstateCustomerInfra>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+    ) -> Result<()> {
+        process_reinstate_customer_infra(ctx)?;
+        Ok(())
+    }
+    pub fn lift_expired_customer_infra_suspension(
+        ctx: Context<LiftExpiredCustomerInfraSuspension>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+    ) -> Result<()> {
+        process_lift_expired_customer_infra_suspension(ctx)?;
         Ok(())
     }
     pub fn driver_infra_slash(
         ctx: Context<DriverInfraSlash>,
         _alpha3_country_code: String,
         driver_infra_count: u64,
-        base_slash_multiplier: f32,
+        slash_multiplier_basis_point: u64,
+        reason: SlashReason,
+        job: Option<Pubkey>,
+        dispute: Option<Pubkey>,
     ) -> Result<()> {
-        process_driver_infra_slash(ctx, driver_infra_count, base_slash_multiplier)?;
+        process_driver_infra_slash(
+            ctx,
+            driver_infra_count,
+            slash_multiplier_basis_point,
+            reason,
+            job,
+            dispute,
+        )?;
         Ok(())
     }
     pub fn customer_infra_slash(
         ctx: Context<CustomerInfraSlash>,
         _alpha3_country_code: String,
         customer_infra_count: u64,
-        base_slash_multiplier: f32,
+        slash_multiplier_basis_point: u64,
+        reason: SlashReason,
+        job: Option<Pubkey>,
+        dispute: Option<Pubkey>,
     ) -> Result<()> {
-        process_customer_infra_slash(ctx, customer_infra_count, base_slash_multiplier)?;
+        process_customer_infra_slash(
+            ctx,
+            customer_infra_count,
+            slash_multiplier_basis_point,
+            reason,
+            job,
+            dispute,
+        )?;
+        Ok(())
+    }
+
+    // SLASH APPEAL
+    pub fn appeal_driver_infra_slash(
+        ctx: Context<AppealDriverInfraSlash>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _slash_count: u64,
+    ) -> Result<()> {
+        process_appeal_driver_infra_slash(ctx)?;
+        Ok(())
+    }
+    pub fn appeal_customer_infra_slash(
+        ctx: Context<AppealCustomerInfraSlash>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        _slash_count: u64,
+    ) -> Result<()> {
+        process_appeal_customer_infra_slash(ctx)?;
+        Ok(())
+    }
+    pub fn reverse_driver_infra_slash(
+        ctx: Context<ReverseDriverInfraSlash>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _slash_count: u64,
+    ) -> Result<()> {
+        process_reverse_driver_infra_slash(ctx)?;
+        Ok(())
+    }
+    pub fn reverse_customer_infra_slash(
+        ctx: Context<ReverseCustomerInfraSlash>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        _slash_count: u64,
+    ) -> Result<()> {
+        process_reverse_customer_infra_slash(ctx)?;
+        Ok(())
+    }
+
+    // DRIVER PROFILE
+    pub fn init_driver_profile(
+        ctx: Context<InitDriverProfile>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        driver_uuid: String,
+        license_expiry: u64,
+        credential_expiry: u64,
+    ) -> Result<()> {
+        process_init_driver_profile(ctx, driver_uuid, license_expiry, credential_expiry)?;
+        Ok(())
+    }
+    pub fn update_driver_profile_credential(
+        ctx: Context<UpdateDriverProfileCredential>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _driver_uuid: String,
+        license_expiry: u64,
+        credential_expiry: u64,
+    ) -> Result<()> {
+        process_update_driver_profile_credential(ctx, license_expiry, credential_expiry)?;
         Ok(())
     }
 
@@ -207,8 +425,34 @@ pub mod ride_network {
         process_driver_update_location(ctx, current_location, next_location)?;
         Ok(())
     }
+    // DRIVER SET AVAILABILITY
+    pub fn driver_set_availability(
+        ctx: Context<DriverSetAvailability>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _driver_uuid: String,
+        availability: DriverAvailability,
+    ) -> Result<()> {
+        process_driver_set_availability(ctx, availability)?;
+        Ok(())
+    }
+    // MARK STALE DRIVER, PERMISSIONLESS
+    pub fn mark_stale_driver(
+        ctx: Context<MarkStaleDriver>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _driver_uuid: String,
+    ) -> Result<()> {
+        process_mark_stale_driver(ctx)?;
+        Ok(())
+    }
     // DRIVER END WORK
-    pub fn driver_end_work(ctx: Context<DriverEndWork>, _driver_uuid: String) -> Result<()> {
+    pub fn driver_end_work(
+        ctx: Context<DriverEndWork>,
+        _alpha3_country_code: String,
+        _driver_uuid: String,
+        _driver_infra_count: u64,
+    ) -> Result<()> {
         process_driver_end_work(ctx)?;
         Ok(())
     }
@@ -218,10 +462,21 @@ pub mod ride_network {
         _alpha3_country_code: String,
         _driver_uuid: String,
         _driver_infra_count: u64,
+        customer_infra_count: u64,
+        job_count: u64,
+    ) -> Result<()> {
+        process_driver_complete_job(ctx, customer_infra_count, job_count)?;
+        Ok(())
+    }
+    // SETTLE JOB AFTER FINALIZE DURATION, PERMISSIONLESS
+    pub fn settle_job(
+        ctx: Context<SettleJob>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
         _customer_infra_count: u64,
         job_count: u64,
     ) -> Result<()> {
-        process_driver_complete_job(ctx, job_count)?;
+        process_settle_job(ctx, job_count)?;
         Ok(())
     }
     // DRIVER ACCEPTED JOB TRIGGERED BY CUSTOMER INFRA
@@ -238,13 +493,25 @@ pub mod ride_network {
         Ok(())
     }
 
+    // DRIVER ARRIVED AT PICKUP
+    pub fn driver_arrived(
+        ctx: Context<DriverArrived>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _driver_uuid: String,
+        _job_count: u64,
+    ) -> Result<()> {
+        process_driver_arrived(ctx)?;
+        Ok(())
+    }
+
     // DRIVER PICKUP CUSTOMER
     pub fn driver_pickup_customer(
         ctx: Context<DriverPickupCustomer>,
         _alpha3_country_code: String,
         _driver_infra_count: u64,
         customer_infra_count: u64,
-        _service_count: u64,
+        _job_count: u64,
     ) -> Result<()> {
         process_driver_pickup_customer(ctx, customer_infra_count)?;
         Ok(())
@@ -263,13 +530,67 @@ pub mod ride_network {
         Ok(())
     }
 
+    // DRIVER REPORT NO SHOW
+    pub fn driver_report_no_show(
+        ctx: Context<DriverReportNoShow>,
+        _alpha3_country_code: String,
+        _driver_uuid: String,
+        _driver_infra_count: u64,
+        _customer_infra_count: u64,
+        job_count: u64,
+    ) -> Result<()> {
+        process_driver_report_no_show(ctx, job_count)?;
+        Ok(())
+    }
+
     // DRIVER RAISE ISSUE
     pub fn driver_raise_issue(
         ctx: Context<DriverRaiseIssue>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _job_count: u64,
+        reason: DisputeReason,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        process_driver_raise_issue(ctx, reason, evidence_hash)?;
+        Ok(())
+    }
+
+    // SUBMIT DISPUTE EVIDENCE, DRIVER OR CUSTOMER INFRA
+    pub fn submit_dispute_evidence(
+        ctx: Context<SubmitDisputeEvidence>,
+        _alpha3_country_code: String,
         _driver_infra_count: u64,
+        _customer_infra_count: u64,
         _job_count: u64,
+        evidence_hash: [u8; 32],
+    ) -> Result<()> {
+        process_submit_dispute_evidence(ctx, evidence_hash)?;
+        Ok(())
+    }
+
+    // RESOLVE DISPUTE BY COUNTRY AUTHORITY
+    pub fn resolve_dispute(
+        ctx: Context<ResolveDispute>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _customer_infra_count: u64,
+        job_count: u64,
+        driver_award_basis_point: u16,
     ) -> Result<()> {
-        process_driver_raise_issue(ctx)?;
+        process_resolve_dispute(ctx, job_count, driver_award_basis_point)?;
+        Ok(())
+    }
+
+    // CLAIM DEFAULT JUDGMENT AFTER DISPUTE WAITOUT, PERMISSIONLESS
+    pub fn claim_default_judgment(
+        ctx: Context<ClaimDefaultJudgment>,
+        _alpha3_country_code: String,
+        _driver_infra_count: u64,
+        _customer_infra_count: u64,
+        job_count: u64,
+    ) -> Result<()> {
+        process_claim_default_judgment(ctx, job_count)?;
         Ok(())
     }
 
@@ -304,55 +625,134 @@ pub mod ride_network {
         Ok(())
     }
 
+    // TOP UP CUSTOMER INFRA DEPOSIT
+    pub fn top_up_customer_infra_deposit(
+        ctx: Context<TopUpCustomerInfraDeposit>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        amount: u64,
+    ) -> Result<()> {
+        process_top_up_customer_infra_deposit(ctx, amount)?;
+        Ok(())
+    }
+
+    // REQUEST CUSTOMER INFRA DEPOSIT WITHDRAWAL
+    pub fn request_customer_infra_withdrawal(
+        ctx: Context<RequestCustomerInfraWithdrawal>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        amount: u64,
+    ) -> Result<()> {
+        process_request_customer_infra_withdrawal(ctx, amount)?;
+        Ok(())
+    }
+
+    // COMPLETE CUSTOMER INFRA DEPOSIT WITHDRAWAL AFTER UNBONDING
+    pub fn complete_customer_infra_withdrawal(
+        ctx: Context<CompleteCustomerInfraWithdrawal>,
+        _alpha3_country_code: String,
+        customer_infra_count: u64,
+    ) -> Result<()> {
+        process_complete_customer_infra_withdrawal(ctx, customer_infra_count)?;
+        Ok(())
+    }
+
+    // FREEZE UNDERFUNDED CUSTOMER INFRA, PERMISSIONLESS
+    pub fn freeze_underfunded_customer_infra(
+        ctx: Context<FreezeUnderfundedCustomerInfra>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+    ) -> Result<()> {
+        process_freeze_underfunded_customer_infra(ctx)?;
+        Ok(())
+    }
+
     // CUSTOMER START OR UPDATE
     pub fn customer_request_ride(
         ctx: Context<CustomerRequestRide>,
         _alpha3_country_code: String,
-        driver_uuid: String,
+        _driver_uuid: String,
         job_count: u64,
         _customer_infra_count: u64,
         _driver_infra_count: u64,
         _distribution_len: u8,
         encrypted_data: String,
         encrypted_combined_rand_base64: String,
-        total_fees: u64,
+        driver_quote: DriverQuote,
+        trip: RideTripParam,
     ) -> Result<()> {
         process_customer_request_ride(
             ctx,
             job_count,
-            driver_uuid,
             encrypted_data,
             encrypted_combined_rand_base64,
-            total_fees,
+            driver_quote,
+            trip,
         )?;
         Ok(())
     }
 
+    // CUSTOMER RESERVE DRIVER
+    pub fn customer_reserve_driver(
+        ctx: Context<CustomerReserveDriver>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        _driver_infra_count: u64,
+        _driver_uuid: String,
+        hold_sec: u64,
+    ) -> Result<()> {
+        process_customer_reserve_driver(ctx, hold_sec)?;
+        Ok(())
+    }
+
+    // CUSTOMER RELEASE DRIVER
+    pub fn customer_release_driver(
+        ctx: Context<CustomerReleaseDriver>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
+        _driver_uuid: String,
+    ) -> Result<()> {
+        process_customer_release_driver(ctx)?;
+        Ok(())
+    }
+
     // CUSTOMER CANCEL RIDE
     pub fn customer_cancel_ride(
         ctx: Context<CustomerCancelRide>,
         _alpha3_country_code: String,
         _driver_uuid: String,
         customer_infra_count: u64,
+        driver_infra_count: u64,
+        job_counter: u64,
+    ) -> Result<()> {
+        process_customer_cancel_ride(ctx, customer_infra_count, driver_infra_count, job_counter)?;
+        Ok(())
+    }
+
+    // CUSTOMER RATE DRIVER
+    pub fn customer_rate_driver(
+        ctx: Context<CustomerRateDriver>,
+        _alpha3_country_code: String,
+        _customer_infra_count: u64,
         _driver_infra_count: u64,
-        _job_counter: u64,
+        _job_count: u64,
+        rating: u8,
     ) -> Result<()> {
-        process_customer_cancel_ride(ctx, customer_infra_count)?;
+        process_customer_rate_driver(ctx, rating)?;
         Ok(())
     }
 
-    // CUSTOMER CANCEL RIDE
+    // CUSTOMER RAISE ISSUE
     pub fn customer_raise_issue(
         ctx: Context<CustomerRaiseIssue>,
+        _alpha3_country_code: String,
         _customer_infra_count: u64,
         _driver_infra_count: u64,
         _job_counter: u64,
+        reason: DisputeReason,
+        evidence_hash: [u8; 32],
     ) -> Result<()> {
-        process_customer_raise_issue(ctx)?;
+        process_customer_raise_issue(ctx, reason, evidence_hash)?;
         Ok(())
     }
 }
-
-fn is_valid_coordinate(coordinate: f64) -> bool {
-    coordinate >= -90.0 && coordinate <= 90.0
-}
@@ -0,0 +1,443 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::fee_math;
+use crate::state::{Driver, Job};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Status {
+    Init,
+    JobAccepted,
+    RejectedByDriver,
+    Arrived,
+    Started,
+    Completed,
+    CancelledByDriver,
+    CancelledByCustomer,
+    DisputeByDriver,
+    DisputeByCustomer,
+    CustomerNoShow,
+}
+
+// Party allowed to move a job from one status to another
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Actor {
+    DriverInfra,
+    CustomerInfra,
+}
+
+// Whether a driver can be matched, Busy is driven by the job lifecycle only
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DriverAvailability {
+    Online,
+    Busy,
+    OnBreak,
+    Offline,
+}
+
+// Every legal transition: (from, to, triggered by)
+#[rustfmt::skip]
+const TRANSITIONS: &[(Status, Status, Actor)] = &[
+    // Matching
+    (Status::Init, Status::JobAccepted, Actor::CustomerInfra),
+    (Status::Init, Status::RejectedByDriver, Actor::DriverInfra),
+    (Status::Init, Status::CancelledByCustomer, Actor::CustomerInfra),
+    // Heading to pickup
+    (Status::JobAccepted, Status::Arrived, Actor::DriverInfra),
+    (Status::JobAccepted, Status::CancelledByDriver, Actor::DriverInfra),
+    (Status::JobAccepted, Status::CancelledByCustomer, Actor::CustomerInfra),
+    (Status::JobAccepted, Status::DisputeByDriver, Actor::DriverInfra),
+    (Status::JobAccepted, Status::DisputeByCustomer, Actor::CustomerInfra),
+    // Waiting at pickup
+    (Status::Arrived, Status::Started, Actor::DriverInfra),
+    (Status::Arrived, Status::CancelledByDriver, Actor::DriverInfra),
+    (Status::Arrived, Status::CancelledByCustomer, Actor::CustomerInfra),
+    (Status::Arrived, Status::DisputeByDriver, Actor::DriverInfra),
+    (Status::Arrived, Status::DisputeByCustomer, Actor::CustomerInfra),
+    (Status::Arrived, Status::CustomerNoShow, Actor::DriverInfra),
+    // On trip
+    (Status::Started, Status::Completed, Actor::DriverInfra),
+    (Status::Started, Status::DisputeByDriver, Actor::DriverInfra),
+    (Status::Started, Status::DisputeByCustomer, Actor::CustomerInfra),
+    // Within finalize duration
+    (Status::Completed, Status::DisputeByDriver, Actor::DriverInfra),
+    (Status::Completed, Status::DisputeByCustomer, Actor::CustomerInfra),
+];
+
+impl Status {
+    pub fn check_transition(self, next: Status, actor: Actor) -> Result<()> {
+        match self {
+            Status::RejectedByDriver => return err!(ErrorCode::RejectedByDriver),
+            Status::CancelledByDriver => return err!(ErrorCode::CancelledByDriver),
+            Status::CancelledByCustomer => return err!(ErrorCode::CancelledByCustomer),
+            Status::DisputeByDriver => return err!(ErrorCode::JobDisputeByDriver),
+            Status::DisputeByCustomer => return err!(ErrorCode::JobDisputeByCustomer),
+            Status::CustomerNoShow => return err!(ErrorCode::CustomerNoShow),
+            _ => {}
+        }
+
+        let mut legal = TRANSITIONS
+            .iter()
+            .filter(|(from, to, _)| *from == self && *to == next)
+            .peekable();
+
+        if legal.peek().is_none() {
+            return match (self, next) {
+                (Status::JobAccepted, Status::JobAccepted) => err!(ErrorCode::JobAlreadyAccepted),
+                (Status::Init, Status::Completed) => err!(ErrorCode::JobNotYetStarted),
+                (_, Status::Arrived) => err!(ErrorCode::IncorrectLifecycleJobAccepted),
+                (_, Status::Started) => err!(ErrorCode::IncorrectLifecycleArrived),
+                (_, Status::Completed) => err!(ErrorCode::IncorrectLifecycleStarted),
+                _ => err!(ErrorCode::InvalidStatusTransition),
+            };
+        }
+
+        if !legal.any(|(_, _, allowed)| *allowed == actor) {
+            return err!(ErrorCode::UnauthorizedStatusTransition);
+        }
+
+        Ok(())
+    }
+}
+
+impl Job {
+    // Validate and apply a status change, stamping when it happened
+    pub fn transition(&mut self, next: Status, actor: Actor, now: u64) -> Result<()> {
+        self.status.check_transition(next, actor)?;
+        if self.status == Status::Completed {
+            self.check_dispute_window(now)?;
+        }
+
+        match next {
+            Status::JobAccepted => self.job_start_time = Some(now),
+            Status::Arrived => self.arrival_time = Some(now),
+            Status::Started => self.pickup_time = Some(now),
+            Status::Completed => self.job_end_time = Some(now),
+            _ => {}
+        }
+        msg!("job status: {:?} -> {:?}", self.status, next);
+        self.status = next;
+        self.status_updated_time = now;
+
+        Ok(())
+    }
+
+    // A completed job can only be disputed until it is finalized
+    fn check_dispute_window(&self, now: u64) -> Result<()> {
+        let job_end_time = self.job_end_time.unwrap_or_default();
+        let finalize_time = fee_math::checked_add(job_end_time, self.terms.finalize_duration_sec)?;
+        if now > finalize_time {
+            return err!(ErrorCode::FinalizeDurationOver);
+        }
+        Ok(())
+    }
+}
+
+impl DriverAvailability {
+    // Manual changes only move between Online, OnBreak and Offline
+    pub fn check_manual_change(self, next: DriverAvailability) -> Result<()> {
+        if self == DriverAvailability::Busy {
+            return err!(ErrorCode::DriverHasActiveJob);
+        }
+        if next == DriverAvailability::Busy {
+            return err!(ErrorCode::InvalidAvailability);
+        }
+        Ok(())
+    }
+}
+
+impl Driver {
+    // Live reservation held by another customer infra blocks matching
+    pub fn check_reservation(&self, customer_infra: Pubkey, now: u64) -> Result<()> {
+        match self.reserved_by {
+            Some(holder) if holder != customer_infra && now < self.reserved_until => {
+                err!(ErrorCode::DriverReserved)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Soft-lock the driver while the customer confirms, a live hold cannot be extended
+    pub fn reserve(&mut self, customer_infra: Pubkey, now: u64, hold_sec: u64) -> Result<()> {
+        self.check_reservation(customer_infra, now)?;
+        if self.reserved_by == Some(customer_infra) && now < self.reserved_until {
+            return err!(ErrorCode::ReservationAlreadyHeld);
+        }
+        self.reserved_by = Some(customer_infra);
+        self.reserved_until = fee_math::checked_add(now, hold_sec)?;
+        Ok(())
+    }
+
+    pub fn clear_reservation(&mut self) {
+        self.reserved_by = None;
+        self.reserved_until = 0;
+    }
+
+    // New job takes the driver and consumes any reservation
+    pub fn assign_job(&mut self, job: Pubkey, customer_infra: Pubkey, now: u64) -> Result<()> {
+        if self.active_job.is_some() {
+            return err!(ErrorCode::DriverAlreadyBooked);
+        }
+        self.check_reservation(customer_infra, now)?;
+        self.active_job = Some(job);
+        self.clear_reservation();
+        Ok(())
+    }
+
+    // No location update within the country heartbeat timeout
+    pub fn is_stale(&self, now: u64, heartbeat_timeout_sec: u64) -> Result<bool> {
+        let heartbeat_deadline =
+            fee_math::checked_add(self.location_last_update, heartbeat_timeout_sec)?;
+        Ok(now > heartbeat_deadline)
+    }
+
+    // Job no longer needs the driver, back to matching
+    pub fn release_from_job(&mut self, job: Pubkey) {
+        if self.active_job != Some(job) {
+            return;
+        }
+        self.active_job = None;
+        if self.availability == DriverAvailability::Busy {
+            self.availability = DriverAvailability::Online;
+        }
+        self.next_location = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{Coordinates, JobTerms};
+
+    #[test]
+    fn happy_path_is_legal() {
+        let path = [
+            (Status::Init, Status::JobAccepted, Actor::CustomerInfra),
+            (Status::JobAccepted, Status::Arrived, Actor::DriverInfra),
+            (Status::Arrived, Status::Started, Actor::DriverInfra),
+            (Status::Started, Status::Completed, Actor::DriverInfra),
+        ];
+        for (from, to, actor) in path {
+            assert!(
+                from.check_transition(to, actor).is_ok(),
+                "{:?} -> {:?}",
+                from,
+                to
+            );
+        }
+    }
+
+    #[test]
+    fn customer_can_cancel_after_acceptance() {
+        assert!(Status::JobAccepted
+            .check_transition(Status::CancelledByCustomer, Actor::CustomerInfra)
+            .is_ok());
+        assert_eq!(
+            Status::JobAccepted.check_transition(Status::CancelledByCustomer, Actor::DriverInfra),
+            err!(ErrorCode::UnauthorizedStatusTransition)
+        );
+    }
+
+    #[test]
+    fn skipped_steps_return_lifecycle_errors() {
+        assert_eq!(
+            Status::Init.check_transition(Status::Arrived, Actor::DriverInfra),
+            err!(ErrorCode::IncorrectLifecycleJobAccepted)
+        );
+        assert_eq!(
+            Status::JobAccepted.check_transition(Status::Started, Actor::DriverInfra),
+            err!(ErrorCode::IncorrectLifecycleArrived)
+        );
+        assert_eq!(
+            Status::Init.check_transition(Status::Completed, Actor::DriverInfra),
+            err!(ErrorCode::JobNotYetStarted)
+        );
+        assert_eq!(
+            Status::JobAccepted.check_transition(Status::JobAccepted, Actor::CustomerInfra),
+            err!(ErrorCode::JobAlreadyAccepted)
+        );
+    }
+
+    #[test]
+    fn closed_statuses_are_final() {
+        assert_eq!(
+            Status::CancelledByDriver
+                .check_transition(Status::DisputeByCustomer, Actor::CustomerInfra),
+            err!(ErrorCode::CancelledByDriver)
+        );
+        assert_eq!(
+            Status::DisputeByCustomer.check_transition(Status::DisputeByDriver, Actor::DriverInfra),
+            err!(ErrorCode::JobDisputeByCustomer)
+        );
+        assert_eq!(
+            Status::Init.check_transition(Status::DisputeByDriver, Actor::DriverInfra),
+            err!(ErrorCode::InvalidStatusTransition)
+        );
+    }
+
+    #[test]
+    fn only_the_driver_reports_a_no_show_at_pickup() {
+        assert!(Status::Arrived
+            .check_transition(Status::CustomerNoShow, Actor::DriverInfra)
+            .is_ok());
+        assert_eq!(
+            Status::JobAccepted.check_transition(Status::CustomerNoShow, Actor::DriverInfra),
+            err!(ErrorCode::InvalidStatusTransition)
+        );
+        assert_eq!(
+            Status::Arrived.check_transition(Status::CustomerNoShow, Actor::CustomerInfra),
+            err!(ErrorCode::UnauthorizedStatusTransition)
+        );
+        assert_eq!(
+            Status::CustomerNoShow
+                .check_transition(Status::DisputeByCustomer, Actor::CustomerInfra),
+            err!(ErrorCode::CustomerNoShow)
+        );
+    }
+
+    #[test]
+    fn completed_job_is_disputable_until_finalized() {
+        let mut completed = job(Status::Completed);
+        completed.job_end_time = Some(1_000);
+        completed.terms.finalize_duration_sec = 600;
+
+        assert_eq!(
+            completed
+                .clone()
+                .transition(Status::DisputeByCustomer, Actor::CustomerInfra, 1_601),
+            err!(ErrorCode::FinalizeDurationOver)
+        );
+        assert!(completed
+            .transition(Status::DisputeByDriver, Actor::DriverInfra, 1_600)
+            .is_ok());
+    }
+
+    #[test]
+    fn busy_is_only_set_by_jobs() {
+        assert!(DriverAvailability::Online
+            .check_manual_change(DriverAvailability::OnBreak)
+            .is_ok());
+        assert!(DriverAvailability::Offline
+            .check_manual_change(DriverAvailability::Online)
+            .is_ok());
+        assert_eq!(
+            DriverAvailability::Online.check_manual_change(DriverAvailability::Busy),
+            err!(ErrorCode::InvalidAvailability)
+        );
+        assert_eq!(
+            DriverAvailability::Busy.check_manual_change(DriverAvailability::Offline),
+            err!(ErrorCode::DriverHasActiveJob)
+        );
+    }
+
+    fn job(status: Status) -> Job {
+        Job {
+            status,
+            job_count: 0,
+            customer_infra: Pubkey::default(),
+            driver_infra: Pubkey::default(),
+            driver_uuid: "driver".to_owned(),
+            distribution: vec![],
+            settlement_protocol: None,
+            total_fee_cent: 4_000,
+            job_initialized_time: 0,
+            job_start_time: None,
+            arrival_time: None,
+            job_end_time: None,
+            encrypted_data: "".to_owned(),
+            encrypted_combined_rand_base64: "".to_owned(),
+            pickup_time: None,
+            status_updated_time: 0,
+            service: 0,
+            passenger_types: vec![],
+            passenger_count: 1,
+            driver_rating: None,
+            trip_distance_m: 0,
+            trip_duration_sec: 0,
+            quote_hash: [0; 32],
+            waiting_fee_cent: 0,
+            pickup_eta: None,
+            terms: JobTerms::default(),
+            applied_cancellation_tier: None,
+        }
+    }
+
+    fn driver() -> Driver {
+        Driver {
+            is_initialized: true,
+            location_update_authority: Pubkey::default(),
+            driver_uuid: "driver".to_owned(),
+            rsa_pem_pubkey: "".to_owned(),
+            infra_authority: Pubkey::default(),
+            last_location: Coordinates { lat: 0, long: 0 },
+            location_last_update: 0,
+            next_location: None,
+            country_key: Pubkey::default(),
+            offered_service: vec![0],
+            passenger_types: vec![0],
+            vehicle: Pubkey::default(),
+            number_of_seats: 4,
+            profile: Pubkey::default(),
+            availability: DriverAvailability::Online,
+            active_job: None,
+            reserved_by: None,
+            reserved_until: 0,
+        }
+    }
+
+    #[test]
+    fn reservation_blocks_other_customer_infras_until_expiry() {
+        let holder = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mut driver = driver();
+        driver.reserve(holder, 100, 30).unwrap();
+
+        assert_eq!(
+            driver.reserve(other, 129, 30),
+            err!(ErrorCode::DriverReserved)
+        );
+        assert_eq!(
+            driver.assign_job(Pubkey::new_unique(), other, 129),
+            err!(ErrorCode::DriverReserved)
+        );
+        // Holder cannot extend a live hold, anyone can match once it lapses
+        assert_eq!(
+            driver.reserve(holder, 120, 30),
+            err!(ErrorCode::ReservationAlreadyHeld)
+        );
+        assert!(driver.check_reservation(other, 130).is_ok());
+        assert!(driver.reserve(other, 130, 30).is_ok());
+    }
+
+    #[test]
+    fn one_active_job_per_driver() {
+        let customer_infra = Pubkey::new_unique();
+        let job = Pubkey::new_unique();
+        let mut driver = driver();
+        driver.reserve(customer_infra, 100, 30).unwrap();
+        driver.assign_job(job, customer_infra, 110).unwrap();
+        assert_eq!(driver.reserved_by, None);
+
+        assert_eq!(
+            driver.assign_job(Pubkey::new_unique(), Pubkey::new_unique(), 200),
+            err!(ErrorCode::DriverAlreadyBooked)
+        );
+
+        // Only the job holding the driver can release it
+        driver.availability = DriverAvailability::Busy;
+        driver.release_from_job(Pubkey::new_unique());
+        assert_eq!(driver.active_job, Some(job));
+        driver.release_from_job(job);
+        assert_eq!(driver.active_job, None);
+        assert_eq!(driver.availability, DriverAvailability::Online);
+    }
+
+    #[test]
+    fn stale_only_after_heartbeat_timeout() {
+        let mut driver = driver();
+        driver.location_last_update = 1_000;
+
+        assert_eq!(driver.is_stale(1_060, 60), Ok(false));
+        assert_eq!(driver.is_stale(1_061, 60), Ok(true));
+    }
+}
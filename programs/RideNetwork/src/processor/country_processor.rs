@@ -1,4 +1,6 @@
 use crate::error::ErrorCode;
+use crate::fee_math::{self, Rounding};
+use crate::lifecycle::Actor;
 use crate::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
@@ -21,13 +23,24 @@ pub fn process_init_or_update_country(
         country_state.update_authority = ctx.accounts.authority.key();
         country_state.stable_mint = ctx.accounts.mint.key();
         params.init_new(country_state)?;
+        country_state.min_deposit_raised_time = Clock::get().unwrap().unix_timestamp as u64;
     } else {
         msg!("Updating Country");
         if country_state.update_authority != ctx.accounts.authority.key() {
             return err!(ErrorCode::InvalidUpdateAuthority);
         }
 
+        let min_driver_infra_deposit = country_state.min_driver_infra_deposit;
+        let min_customer_infra_deposit = country_state.min_customer_infra_deposit;
         params.update_or_same(country_state)?;
+
+        // Existing infras get deposit_grace_period_sec to top up from now
+        if country_state.min_driver_infra_deposit > min_driver_infra_deposit
+            || country_state.min_customer_infra_deposit > min_customer_infra_deposit
+            || params.deposit_tiers.is_some()
+        {
+            country_state.min_deposit_raised_time = Clock::get().unwrap().unix_timestamp as u64;
+        }
     }
 
     Ok(())
@@ -46,24 +59,112 @@ pub fn process_approve_driver_infra(ctx: Context<ApproveDriverInfra>) -> Result<
     ctx.accounts.driver_infra.is_verified = true;
     Ok(())
 }
-pub fn process_driver_infra_suspension(ctx: Context<DriverInfraSuspension>) -> Result<()> {
-    ctx.accounts.driver_infra.is_frozen = false;
+pub fn process_driver_infra_suspension(
+    ctx: Context<DriverInfraSuspension>,
+    reason: SuspensionReason,
+    duration_sec: Option<u64>,
+) -> Result<()> {
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+
+    driver_infra.suspension_reason = Some(reason);
+    driver_infra.suspended_until = match duration_sec {
+        Some(duration_sec) => Some(fee_math::checked_add(current_time, duration_sec)?),
+        None => None,
+    };
+    driver_infra.refresh_frozen();
+    Ok(())
+}
+pub fn process_customer_infra_suspension(
+    ctx: Context<CustomerInfraSuspension>,
+    reason: SuspensionReason,
+    duration_sec: Option<u64>,
+) -> Result<()> {
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+
+    customer_infra.suspension_reason = Some(reason);
+    customer_infra.suspended_until = match duration_sec {
+        Some(duration_sec) => Some(fee_math::checked_add(current_time, duration_sec)?),
+        None => None,
+    };
+    customer_infra.refresh_frozen();
+    Ok(())
+}
+pub fn process_reinstate_driver_infra(ctx: Context<ReinstateDriverInfra>) -> Result<()> {
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    // Country clears its own causes, an underfunded deposit still needs a top up
+    driver_infra.suspension_reason = None;
+    driver_infra.suspended_until = None;
+    driver_infra.company_review_pending = false;
+    driver_infra.refresh_frozen();
+    Ok(())
+}
+pub fn process_reinstate_customer_infra(ctx: Context<ReinstateCustomerInfra>) -> Result<()> {
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    // Country clears its own causes, an underfunded deposit still needs a top up
+    customer_infra.suspension_reason = None;
+    customer_infra.suspended_until = None;
+    customer_infra.company_review_pending = false;
+    customer_infra.refresh_frozen();
     Ok(())
 }
-pub fn process_customer_infra_suspension(ctx: Context<CustomerInfraSuspension>) -> Result<()> {
-    ctx.accounts.customer_infra.is_frozen = false;
+// Permissionless, only lifts a timed suspension once it has expired
+pub fn process_lift_expired_driver_infra_suspension(
+    ctx: Context<LiftExpiredDriverInfraSuspension>,
+) -> Result<()> {
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    let suspended_until = match (driver_infra.suspension_reason, driver_infra.suspended_until) {
+        (Some(_), Some(suspended_until)) => suspended_until,
+        _ => return err!(ErrorCode::NoTimedSuspension),
+    };
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time < suspended_until {
+        return err!(ErrorCode::SuspensionNotExpired);
+    }
+
+    // Only the suspension is lifted, other freeze causes stay
+    driver_infra.suspension_reason = None;
+    driver_infra.suspended_until = None;
+    driver_infra.refresh_frozen();
+    Ok(())
+}
+// Permissionless, only lifts a timed suspension once it has expired
+pub fn process_lift_expired_customer_infra_suspension(
+    ctx: Context<LiftExpiredCustomerInfraSuspension>,
+) -> Result<()> {
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    let suspended_until = match (
+        customer_infra.suspension_reason,
+        customer_infra.suspended_until,
+    ) {
+        (Some(_), Some(suspended_until)) => suspended_until,
+        _ => return err!(ErrorCode::NoTimedSuspension),
+    };
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time < suspended_until {
+        return err!(ErrorCode::SuspensionNotExpired);
+    }
+
+    // Only the suspension is lifted, other freeze causes stay
+    customer_infra.suspension_reason = None;
+    customer_infra.suspended_until = None;
+    customer_infra.refresh_frozen();
     Ok(())
 }
-// TODO: on-chain Slash logic?
 pub fn process_driver_infra_slash(
     ctx: Context<DriverInfraSlash>,
     driver_infra_count: u64,
-    base_slash_multiplier: f32,
+    slash_multiplier_basis_point: u64,
+    reason: SlashReason,
+    job: Option<Pubkey>,
+    dispute: Option<Pubkey>,
 ) -> Result<()> {
     // Transfer token driver_stable_account to country_token_account
     let auth_bump = *ctx.bumps.get("driver_infra").unwrap();
     let seeds = &[
         b"driver_infra".as_ref(),
+        ctx.accounts.country_state.alpha3_country_code.as_ref(),
         &driver_infra_count.to_le_bytes(),
         &[auth_bump],
     ];
@@ -76,22 +177,45 @@ pub fn process_driver_infra_slash(
     };
     let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-    let amount_to_slash = (ctx.accounts.country_state.base_slash_amount as f32
-        * base_slash_multiplier)
-        .round() as u64;
+    // Multiplier in basis points, 10,000 slashes exactly the base amount
+    let amount_to_slash = fee_math::apply_basis_point(
+        ctx.accounts.country_state.base_slash_amount,
+        slash_multiplier_basis_point,
+        Rounding::Nearest,
+    )?;
 
     token::transfer(token_transfer_context, amount_to_slash)?;
+
+    let driver_infra = &ctx.accounts.driver_infra;
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    record_slash(
+        &mut ctx.accounts.slash_record,
+        &mut ctx.accounts.country_state,
+        driver_infra.key(),
+        Actor::DriverInfra,
+        reason,
+        job,
+        dispute,
+        slash_multiplier_basis_point,
+        amount_to_slash,
+        current_time,
+    )?;
     Ok(())
 }
 pub fn process_customer_infra_slash(
     ctx: Context<CustomerInfraSlash>,
     customer_infra_count: u64,
-    base_slash_multiplier: f32,
+    slash_multiplier_basis_point: u64,
+    reason: SlashReason,
+    job: Option<Pubkey>,
+    dispute: Option<Pubkey>,
 ) -> Result<()> {
     // Transfer token customer_stable_account to country_token_account
     let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
     let seeds = &[
         b"customer_infra".as_ref(),
+        ctx.accounts.country_state.alpha3_country_code.as_ref(),
         &customer_infra_count.to_le_bytes(),
         &[auth_bump],
     ];
@@ -104,10 +228,29 @@ pub fn process_customer_infra_slash(
     };
     let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-    let amount_to_slash = (ctx.accounts.country_state.base_slash_amount as f32
-        * base_slash_multiplier)
-        .round() as u64;
+    // Multiplier in basis points, 10,000 slashes exactly the base amount
+    let amount_to_slash = fee_math::apply_basis_point(
+        ctx.accounts.country_state.base_slash_amount,
+        slash_multiplier_basis_point,
+        Rounding::Nearest,
+    )?;
 
     token::transfer(token_transfer_context, amount_to_slash)?;
+
+    let customer_infra = &ctx.accounts.customer_infra;
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    record_slash(
+        &mut ctx.accounts.slash_record,
+        &mut ctx.accounts.country_state,
+        customer_infra.key(),
+        Actor::CustomerInfra,
+        reason,
+        job,
+        dispute,
+        slash_multiplier_basis_point,
+        amount_to_slash,
+        current_time,
+    )?;
     Ok(())
 }
@@ -1,4 +1,10 @@
-use crate::{error::ErrorCode, state::*, *};
+use crate::{
+    error::ErrorCode,
+    fee_math::{self, MAX_BASIS_POINT},
+    reputation,
+    state::*,
+    *,
+};
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, transfer, Transfer};
@@ -31,6 +37,15 @@ pub fn process_init_customer_infra(
     customer_infra.cancellation = 0;
     customer_infra.dispute_cases = 0;
     customer_infra.cases_lost_in_dispute = 0;
+    customer_infra.open_jobs = 0;
+    customer_infra.pending_withdrawal_cent = 0;
+    customer_infra.withdrawal_available_time = None;
+    customer_infra.suspension_reason = None;
+    customer_infra.suspended_until = None;
+    customer_infra.completed_ride = 0;
+    customer_infra.reputation_basis_point = MAX_BASIS_POINT as u16;
+    customer_infra.company_review_pending = false;
+    customer_infra.is_underfunded = false;
 
     country_state.customer_infra_counter =
         country_state.customer_infra_counter.checked_add(1).unwrap();
@@ -51,7 +66,8 @@ pub fn process_update_customer_infra_company(
     new_company_info.website = params.website;
 
     infra.company_info_current_count = params.old_company_info_count + 1;
-    infra.is_frozen = true;
+    infra.company_review_pending = true;
+    infra.refresh_frozen();
 
     Ok(())
 }
@@ -64,3 +80,153 @@ pub fn process_update_customer_infra_basis_point(
 
     Ok(())
 }
+
+pub fn process_top_up_customer_infra_deposit(
+    ctx: Context<TopUpCustomerInfraDeposit>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.customer_infra_owner_stable.to_account_info(),
+        to: ctx.accounts.customer_infra_stable.to_account_info(),
+        authority: ctx.accounts.customer_infra_owner.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token::transfer(token_transfer_context, amount)?;
+
+    // A top up back to the country minimum lifts an underfunded freeze
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    if customer_infra.is_underfunded {
+        let country_state = &ctx.accounts.country_state;
+        let required_deposit = reputation::required_deposit(
+            country_state.min_customer_infra_deposit,
+            &country_state.deposit_tiers,
+            customer_infra.reputation_basis_point,
+        )?;
+        ctx.accounts.customer_infra_stable.reload()?;
+        let deposit = reputation::bonded_deposit(
+            ctx.accounts.customer_infra_stable.amount,
+            customer_infra.pending_withdrawal_cent,
+        );
+        if deposit >= required_deposit {
+            customer_infra.is_underfunded = false;
+            customer_infra.refresh_frozen();
+        }
+    }
+
+    Ok(())
+}
+
+pub fn process_request_customer_infra_withdrawal(
+    ctx: Context<RequestCustomerInfraWithdrawal>,
+    amount: u64,
+) -> Result<()> {
+    let customer_infra = &mut ctx.accounts.customer_infra;
+
+    if customer_infra.open_jobs > 0 {
+        return err!(ErrorCode::InfraHasOpenJobs);
+    }
+    if customer_infra.withdrawal_available_time.is_some() {
+        return err!(ErrorCode::WithdrawalAlreadyPending);
+    }
+    // Ride earnings land in the same stable account, all of it can be unbonded
+    let withdrawable = reputation::bonded_deposit(
+        ctx.accounts.customer_infra_stable.amount,
+        customer_infra.pending_withdrawal_cent,
+    );
+    if amount == 0 || amount > withdrawable {
+        return err!(ErrorCode::InsufficientInfraDeposit);
+    }
+
+    // Unbonding deposit no longer counts towards the minimum
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    customer_infra.pending_withdrawal_cent = amount;
+    customer_infra.withdrawal_available_time = Some(fee_math::checked_add(
+        current_time,
+        ctx.accounts.country_state.infra_unbonding_period_sec,
+    )?);
+
+    Ok(())
+}
+
+pub fn process_complete_customer_infra_withdrawal(
+    ctx: Context<CompleteCustomerInfraWithdrawal>,
+    customer_infra_count: u64,
+) -> Result<()> {
+    let customer_infra = &ctx.accounts.customer_infra;
+
+    let withdrawal_available_time = match customer_infra.withdrawal_available_time {
+        Some(time) => time,
+        None => return err!(ErrorCode::NoPendingWithdrawal),
+    };
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time < withdrawal_available_time {
+        return err!(ErrorCode::UnbondingPeriodNotOver);
+    }
+    if customer_infra.open_jobs > 0 {
+        return err!(ErrorCode::InfraHasOpenJobs);
+    }
+
+    // A slash during unbonding can leave less than requested, top up first
+    let amount = customer_infra.pending_withdrawal_cent;
+    if amount > ctx.accounts.customer_infra_stable.amount {
+        return err!(ErrorCode::InsufficientInfraDeposit);
+    }
+
+    let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
+    let seeds = &[
+        b"customer_infra".as_ref(),
+        ctx.accounts.country_state.alpha3_country_code.as_ref(),
+        &customer_infra_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.customer_infra_stable.to_account_info(),
+        to: ctx.accounts.customer_infra_owner_stable.to_account_info(),
+        authority: ctx.accounts.customer_infra.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(token_transfer_context, amount)?;
+
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    customer_infra.pending_withdrawal_cent = 0;
+    customer_infra.withdrawal_available_time = None;
+
+    Ok(())
+}
+
+// Permissionless, freeze once the top up grace period after a minimum deposit raise is over
+pub fn process_freeze_underfunded_customer_infra(
+    ctx: Context<FreezeUnderfundedCustomerInfra>,
+) -> Result<()> {
+    let country_state = &ctx.accounts.country_state;
+    let customer_infra = &mut ctx.accounts.customer_infra;
+
+    let required_deposit = reputation::required_deposit(
+        country_state.min_customer_infra_deposit,
+        &country_state.deposit_tiers,
+        customer_infra.reputation_basis_point,
+    )?;
+    let deposit = reputation::bonded_deposit(
+        ctx.accounts.customer_infra_stable.amount,
+        customer_infra.pending_withdrawal_cent,
+    );
+    if deposit >= required_deposit {
+        return err!(ErrorCode::InfraDepositSufficient);
+    }
+    let grace_period_end = fee_math::checked_add(
+        country_state.min_deposit_raised_time,
+        country_state.deposit_grace_period_sec,
+    )?;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time <= grace_period_end {
+        return err!(ErrorCode::DepositGracePeriodNotOver);
+    }
+
+    customer_infra.is_underfunded = true;
+    customer_infra.refresh_frozen();
+
+    Ok(())
+}
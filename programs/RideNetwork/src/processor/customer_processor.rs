@@ -1,4 +1,13 @@
-use crate::{error::ErrorCode, state::*, *};
+use crate::{
+    cancellation,
+    error::ErrorCode,
+    fare, fee_math, geo,
+    lifecycle::Actor,
+    quote::{self, DriverQuote},
+    reputation,
+    state::*,
+    *,
+};
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, transfer, Transfer};
@@ -6,21 +15,83 @@ use anchor_spl::token::{self, transfer, Transfer};
 pub fn process_customer_request_ride(
     ctx: Context<CustomerRequestRide>,
     job_count: u64,
-    driver_uuid: String,
     encrypted_data: String,
     encrypted_combined_rand_base64: String,
-    total_fees: u64,
+    driver_quote: DriverQuote,
+    trip: RideTripParam,
 ) -> Result<()> {
+    check_driver_capability(
+        &ctx.accounts.driver,
+        driver_quote.service,
+        &trip.passenger_types,
+        trip.passenger_count,
+    )?;
+
     let customer_infra = &mut ctx.accounts.customer_infra;
     let job = &mut ctx.accounts.job;
     let driver_infra = &mut ctx.accounts.driver_infra;
+    let country_state = &ctx.accounts.country_state;
+
+    // Both infras must meet the country reputation threshold to be matched
+    let driver_infra_eligible = reputation::meets_threshold(
+        driver_infra.reputation_basis_point,
+        driver_infra.matched_ride,
+        country_state.min_reputation_basis_point,
+        country_state.reputation_min_matched_ride,
+    );
+    let customer_infra_eligible = reputation::meets_threshold(
+        customer_infra.reputation_basis_point,
+        customer_infra.matched_ride,
+        country_state.min_reputation_basis_point,
+        country_state.reputation_min_matched_ride,
+    );
+    if !driver_infra_eligible || !customer_infra_eligible {
+        return err!(ErrorCode::ReputationBelowThreshold);
+    }
+
+    // Fare cannot undercut the rate card for the committed trip
+    let minimum_fare = fare::compute_fare(
+        country_state.base_rate_cent,
+        country_state.min_km_rate_cent,
+        country_state.min_min_fee_cent,
+        trip.trip_distance_m,
+        trip.trip_duration_sec,
+    )?;
+    if driver_quote.fare_cent < minimum_fare {
+        return err!(ErrorCode::FareBelowRateCard);
+    }
+
+    // Fare must be the one the driver infra signed, in this transaction
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time > driver_quote.expiry {
+        return err!(ErrorCode::QuoteExpired);
+    }
+    if driver_quote.nonce <= ctx.accounts.driver_profile.quote_nonce {
+        return err!(ErrorCode::QuoteNonceUsed);
+    }
+    quote::verify_quote_signature(
+        &ctx.accounts.instructions,
+        &driver_infra.update_authority,
+        &driver_quote,
+    )?;
+    job.quote_hash = driver_quote.hash()?;
+    ctx.accounts.driver_profile.quote_nonce = driver_quote.nonce;
+
+    // Driver can only be held by one job, and not while reserved by another customer infra
+    ctx.accounts
+        .driver
+        .assign_job(job.key(), customer_infra.key(), current_time)?;
 
     job.driver_infra = driver_infra.key();
     job.job_count = job_count;
-    job.driver_uuid = driver_uuid;
+    driver_infra.job_counter += 1;
+    driver_infra.open_jobs += 1;
+    customer_infra.open_jobs += 1;
+    job.driver_uuid = driver_quote.driver_uuid;
     job.customer_infra = customer_infra.key();
-    job.job_initialized_time = Clock::get().unwrap().unix_timestamp as u64;
-    job.total_fee_cent = total_fees;
+    job.job_initialized_time = current_time;
+    job.status_updated_time = job.job_initialized_time;
+    job.total_fee_cent = driver_quote.fare_cent;
     let driver_share = Distribution {
         provider: driver_infra.key(),
         basis_point_payout: driver_infra.driver_infra_fee_basis_point,
@@ -29,13 +100,33 @@ pub fn process_customer_request_ride(
         provider: customer_infra.key(),
         basis_point_payout: customer_infra.customer_infra_fee_basis_point,
     };
+    // Platform fees to country and global treasuries
+    let country_share = Distribution {
+        provider: ctx.accounts.country_state.key(),
+        basis_point_payout: ctx.accounts.country_state.platform_fee_basis_point,
+    };
+    let global_share = Distribution {
+        provider: ctx.accounts.global_state.key(),
+        basis_point_payout: ctx.accounts.global_state.platform_fee_basis_point,
+    };
 
     job.distribution.push(driver_share);
     job.distribution.push(customer_share);
+    job.distribution.push(country_share);
+    job.distribution.push(global_share);
+    validate_distribution(&job.distribution)?;
 
     job.status = Status::Init;
     job.encrypted_data = encrypted_data;
     job.encrypted_combined_rand_base64 = encrypted_combined_rand_base64;
+    job.service = driver_quote.service;
+    job.passenger_types = trip.passenger_types;
+    job.passenger_count = trip.passenger_count;
+    job.trip_distance_m = trip.trip_distance_m;
+    job.trip_duration_sec = trip.trip_duration_sec;
+    // Later instructions use these terms, not the live country
+    job.terms = JobTerms::snapshot(country_state);
+    job.applied_cancellation_tier = None;
 
     // Transfer funds to Escrow
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -45,8 +136,52 @@ pub fn process_customer_request_ride(
         authority: ctx.accounts.customer_infra_owner.to_account_info(),
     };
     let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
-    transfer(token_transfer_context, total_fees)?;
+    transfer(token_transfer_context, driver_quote.fare_cent)?;
+
+    Ok(())
+}
 
+pub fn process_customer_reserve_driver(
+    ctx: Context<CustomerReserveDriver>,
+    hold_sec: u64,
+) -> Result<()> {
+    if hold_sec == 0 || hold_sec > ctx.accounts.country_state.max_reservation_hold_sec {
+        return err!(ErrorCode::InvalidReservationHold);
+    }
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let customer_infra = ctx.accounts.customer_infra.key();
+    ctx.accounts
+        .driver
+        .reserve(customer_infra, current_time, hold_sec)?;
+
+    Ok(())
+}
+
+pub fn process_customer_release_driver(ctx: Context<CustomerReleaseDriver>) -> Result<()> {
+    ctx.accounts.driver.clear_reservation();
+    Ok(())
+}
+
+// Targeted driver must offer the service, accept every passenger type and seat everyone
+fn check_driver_capability(
+    driver: &Driver,
+    service: u64,
+    passenger_types: &[u64],
+    passenger_count: u8,
+) -> Result<()> {
+    if !driver.offered_service.contains(&service) {
+        return err!(ErrorCode::ServiceNotOffered);
+    }
+    if passenger_types
+        .iter()
+        .any(|passenger_type| !driver.passenger_types.contains(passenger_type))
+    {
+        return err!(ErrorCode::PassengerTypeNotAccepted);
+    }
+    if passenger_count == 0 || passenger_count > driver.number_of_seats {
+        return err!(ErrorCode::InvalidPassengerCount);
+    }
     Ok(())
 }
 
@@ -57,83 +192,182 @@ pub fn process_driver_accepted_job(
     let driver = &mut ctx.accounts.driver;
     let job = &mut ctx.accounts.job;
 
-    // This shouldn't happen => Job account should be closed
-    if job.status == Status::CancelledByCustomer {
-        return err!(ErrorCode::CancelledByCustomer);
-    }
+    next_location.validate()?;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    job.transition(Status::JobAccepted, Actor::CustomerInfra, current_time)?;
+    driver.next_location = Some(next_location);
+    driver.availability = DriverAvailability::Busy;
 
-    if job.status == Status::JobAccepted {
-        return err!(ErrorCode::JobAlreadyAccepted);
+    // Pickup ETA from the driver's last location at the agreed pickup speed
+    let pickup_speed = job.terms.pickup_speed_m_per_sec;
+    if pickup_speed > 0 {
+        let pickup_distance = geo::haversine_distance_m(&driver.last_location, &next_location)?;
+        let travel_sec =
+            fee_math::mul_div(pickup_distance, 1, pickup_speed, fee_math::Rounding::Up)?;
+        job.pickup_eta = Some(fee_math::checked_add(current_time, travel_sec)?);
     }
 
-    if job.status == Status::Init {
-        driver.next_location = Some(next_location);
-        job.status = Status::JobAccepted;
-        job.job_start_time = Some(Clock::get().unwrap().unix_timestamp as u64);
-        let driver_share = Distribution {
-            provider: ctx.accounts.driver_infra.key(),
-            basis_point_payout: ctx.accounts.driver_infra.driver_infra_fee_basis_point,
-        };
-
-        let is_matching = job.distribution.iter().any(|item| {
-            item.provider == driver_share.provider
-                && item.basis_point_payout == driver_share.basis_point_payout
-        });
-
-        if !is_matching {
-            return err!(ErrorCode::MismatchDriverPayout);
-        }
+    let driver_share = Distribution {
+        provider: ctx.accounts.driver_infra.key(),
+        basis_point_payout: ctx.accounts.driver_infra.driver_infra_fee_basis_point,
+    };
+
+    let is_matching = job.distribution.iter().any(|item| {
+        item.provider == driver_share.provider
+            && item.basis_point_payout == driver_share.basis_point_payout
+    });
+
+    if !is_matching {
+        return err!(ErrorCode::MismatchDriverPayout);
     }
 
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    driver_infra.matched_ride += 1;
+    reputation::refresh_driver_infra(driver_infra);
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    customer_infra.matched_ride += 1;
+    reputation::refresh_customer_infra(customer_infra);
+
     Ok(())
 }
 
 pub fn process_customer_cancel_ride(
     ctx: Context<CustomerCancelRide>,
     customer_infra_count: u64,
+    driver_infra_count: u64,
+    job_counter: u64,
 ) -> Result<()> {
     let driver = &mut ctx.accounts.driver;
     let job = &mut ctx.accounts.job;
     let country_state = &mut ctx.accounts.country_state;
 
-    // Driver already cancelled
-    if job.status == Status::CancelledByDriver {
-        return err!(ErrorCode::CancelledByDriver);
-    }
-    // Dispute raised by driver, settle off-chain
-    if job.status == Status::JobAccepted {
-        return err!(ErrorCode::JobDisputeByDriver);
-    }
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Cancellation schedule agreed at request, evaluated on the stage being left
+    let cancellation_tier = cancellation::applicable_tier(
+        &job.terms.cancellation_tiers,
+        Actor::CustomerInfra,
+        job,
+        current_time,
+    );
+    job.transition(
+        Status::CancelledByCustomer,
+        Actor::CustomerInfra,
+        current_time,
+    )?;
+
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let job_seeds = &[
+        b"job".as_ref(),
+        &ctx.accounts.driver_infra.key().to_bytes(),
+        &job_counter.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let job_signer = &[&job_seeds[..]];
+    let escrowed = ctx.accounts.job_esrow_stable.amount;
+    let mut fee_from_escrow = 0;
+    let fee = match cancellation_tier {
+        Some(tier) => tier.fee(job.total_fee_cent)?,
+        None => 0,
+    };
 
     // Lifecycle requirement
-    // Driver found, else proceed to close accounts
-    if job.status == Status::JobAccepted && job.job_start_time.is_some() {
-        job.status = Status::CancelledByCustomer;
-
-        let current_time = Clock::get().unwrap().unix_timestamp as u64;
-        if current_time - country_state.customer_cancellation_fee_sec > job.job_start_time.unwrap()
-        {
-            // Process to transfer customer_infra to driver_infra
-            let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
-            let seeds = &[
-                b"customer_infra".as_ref(),
-                &customer_infra_count.to_le_bytes(),
-                &[auth_bump],
-            ];
-            let signer = &[&seeds[..]];
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.customer_infra_stable.to_account_info(),
-                to: ctx.accounts.driver_infra_stable.to_account_info(),
-                authority: ctx.accounts.customer_infra.to_account_info(),
-            };
-            let token_transfer_context =
-                CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-
-            token::transfer(token_transfer_context, country_state.cancellation_fee_cent)?;
+    // Customer_infra pays the driver_infra, unless the driver breached the pickup ETA
+    if let Some(tier) = cancellation_tier {
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        match tier.charged_party() {
+            Actor::CustomerInfra if fee > 0 => {
+                // Out of the escrowed fare first
+                fee_from_escrow = fee.min(escrowed);
+                transfer_from_escrow(
+                    job,
+                    job_signer,
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.job_esrow_stable,
+                    &ctx.accounts.driver_infra_stable,
+                    fee_from_escrow,
+                )?;
+                // A flat fee above the fare, transfer customer_infra to driver_infra
+                let fee_over_escrow = fee - fee_from_escrow;
+                if fee_over_escrow > 0 {
+                    let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
+                    let seeds = &[
+                        b"customer_infra".as_ref(),
+                        country_state.alpha3_country_code.as_ref(),
+                        &customer_infra_count.to_le_bytes(),
+                        &[auth_bump],
+                    ];
+                    let signer = &[&seeds[..]];
+                    let cpi_accounts = Transfer {
+                        from: ctx.accounts.customer_infra_stable.to_account_info(),
+                        to: ctx.accounts.driver_infra_stable.to_account_info(),
+                        authority: ctx.accounts.customer_infra.to_account_info(),
+                    };
+                    let token_transfer_context =
+                        CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                    token::transfer(token_transfer_context, fee_over_escrow)?;
+                }
+            }
+            Actor::DriverInfra if fee > 0 => {
+                // Process to transfer driver_infra to customer_infra
+                let auth_bump = *ctx.bumps.get("driver_infra").unwrap();
+                let seeds = &[
+                    b"driver_infra".as_ref(),
+                    country_state.alpha3_country_code.as_ref(),
+                    &driver_infra_count.to_le_bytes(),
+                    &[auth_bump],
+                ];
+                let signer = &[&seeds[..]];
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.driver_infra_stable.to_account_info(),
+                    to: ctx.accounts.customer_infra_stable.to_account_info(),
+                    authority: ctx.accounts.driver_infra.to_account_info(),
+                };
+                let token_transfer_context =
+                    CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                token::transfer(token_transfer_context, fee)?;
+            }
+            _ => {}
         }
     }
 
+    job.applied_cancellation_tier = cancellation_tier;
+    emit!(JobCancelled {
+        job: job.key(),
+        status: job.status,
+        tier: cancellation_tier,
+        fee_cent: fee,
+    });
+
+    // Rest of the escrowed fare back to customer_infra
+    transfer_from_escrow(
+        job,
+        job_signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &ctx.accounts.customer_infra_stable,
+        fee_math::checked_sub(escrowed, fee_from_escrow)?,
+    )?;
+    close_job_escrow(
+        job,
+        job_signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+
+    // Only a cancellation after a match counts against the customer infra
+    if job.job_start_time.is_some() {
+        let customer_infra = &mut ctx.accounts.customer_infra;
+        customer_infra.cancellation += 1;
+        reputation::refresh_customer_infra(customer_infra);
+    }
+    driver.release_from_job(job.key());
+
+    release_open_job(
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+    );
+
     // Close job account
     job.job_start_time = None;
     job.job_end_time = None;
@@ -143,28 +377,56 @@ pub fn process_customer_cancel_ride(
     job.encrypted_combined_rand_base64 = "".to_owned();
     job.close(ctx.accounts.customer_infra.to_account_info())?;
 
-    // Close driver account
-    driver.driver_uuid = "".to_owned();
-    driver.is_initialized = false;
-    driver.next_location = None;
-    driver.close(ctx.accounts.driver_infra.to_account_info())?;
-
     Ok(())
 }
 
-pub fn process_customer_raise_issue(ctx: Context<CustomerRaiseIssue>) -> Result<()> {
+pub fn process_customer_rate_driver(ctx: Context<CustomerRateDriver>, rating: u8) -> Result<()> {
     let job = &mut ctx.accounts.job;
+    let driver_profile = &mut ctx.accounts.driver_profile;
 
-    // Ride already cancelled by driver
-    if job.status == Status::CancelledByDriver {
-        return err!(ErrorCode::CancelledByDriver);
+    // Rated once, between completion and settlement
+    if job.status != Status::Completed {
+        return err!(ErrorCode::IncorrectLifecycleCompleted);
+    }
+    if job.driver_rating.is_some() {
+        return err!(ErrorCode::DriverAlreadyRated);
     }
-    // Driver raised issue, settle off-chain
-    if job.status == Status::DisputeByDriver {
-        return err!(ErrorCode::JobDisputeByDriver);
+    if rating == 0 || rating > MAX_DRIVER_RATING {
+        return err!(ErrorCode::InvalidDriverRating);
     }
 
-    job.status = Status::DisputeByCustomer;
+    job.driver_rating = Some(rating);
+    driver_profile.rating_sum += rating as u64;
+    driver_profile.rating_count += 1;
+
+    Ok(())
+}
+
+pub fn process_customer_raise_issue(
+    ctx: Context<CustomerRaiseIssue>,
+    reason: DisputeReason,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let in_progress = job.status != Status::Completed;
+    job.transition(
+        Status::DisputeByCustomer,
+        Actor::CustomerInfra,
+        current_time,
+    )?;
+    release_disputed_driver(&mut ctx.accounts.driver, job.key(), in_progress)?;
+
+    open_dispute(
+        &mut ctx.accounts.dispute,
+        job.key(),
+        Actor::CustomerInfra,
+        reason,
+        evidence_hash,
+        current_time,
+        job.terms.dispute_waitout_period,
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,281 @@
+use crate::{
+    error::ErrorCode,
+    fee_math::{self, Rounding, MAX_BASIS_POINT},
+    reputation,
+    state::*,
+    *,
+};
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+// Record a newly raised dispute, the counterparty gets dispute_waitout_period to respond
+pub fn open_dispute(
+    dispute: &mut Account<Dispute>,
+    job: Pubkey,
+    opener: Actor,
+    reason: DisputeReason,
+    evidence_hash: [u8; 32],
+    current_time: u64,
+    dispute_waitout_period: u64,
+) -> Result<()> {
+    dispute.job = job;
+    dispute.opener = opener;
+    dispute.reason = reason;
+    match opener {
+        Actor::DriverInfra => dispute.driver_evidence = vec![evidence_hash],
+        Actor::CustomerInfra => dispute.customer_evidence = vec![evidence_hash],
+    }
+    dispute.opened_time = current_time;
+    dispute.response_deadline = fee_math::checked_add(current_time, dispute_waitout_period)?;
+    dispute.responded_time = None;
+    dispute.driver_award_basis_point = None;
+    dispute.resolved_time = None;
+
+    Ok(())
+}
+
+pub fn process_submit_dispute_evidence(
+    ctx: Context<SubmitDisputeEvidence>,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+    let authority = ctx.accounts.authority.key();
+
+    if dispute.resolved_time.is_some() {
+        return err!(ErrorCode::DisputeAlreadyResolved);
+    }
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time > dispute.response_deadline {
+        return err!(ErrorCode::DisputeResponseWindowOver);
+    }
+
+    let party = if authority == ctx.accounts.driver_infra.update_authority {
+        Actor::DriverInfra
+    } else if authority == ctx.accounts.customer_infra.update_authority {
+        Actor::CustomerInfra
+    } else {
+        return err!(ErrorCode::UnauthorizedDisputeParty);
+    };
+
+    let evidence = match party {
+        Actor::DriverInfra => &mut dispute.driver_evidence,
+        Actor::CustomerInfra => &mut dispute.customer_evidence,
+    };
+    if evidence.len() >= MAX_DISPUTE_EVIDENCE {
+        return err!(ErrorCode::TooManyDisputeEvidence);
+    }
+    evidence.push(evidence_hash);
+
+    // First submission from the counterparty counts as the response
+    if party != dispute.opener && dispute.responded_time.is_none() {
+        dispute.responded_time = Some(current_time);
+    }
+
+    Ok(())
+}
+
+pub fn process_resolve_dispute(
+    ctx: Context<ResolveDispute>,
+    job_count: u64,
+    driver_award_basis_point: u16,
+) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+
+    if dispute.resolved_time.is_some() {
+        return err!(ErrorCode::DisputeAlreadyResolved);
+    }
+    // Counterparty either responded or had the full window to do so
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if dispute.responded_time.is_none() && current_time <= dispute.response_deadline {
+        return err!(ErrorCode::DisputeResponseWindowNotOver);
+    }
+    if driver_award_basis_point as u64 > MAX_BASIS_POINT {
+        return err!(ErrorCode::InvalidDistributionBasisPoint);
+    }
+
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let driver_infra_key = ctx.accounts.driver_infra.key();
+    let seeds = &[
+        b"job".as_ref(),
+        driver_infra_key.as_ref(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    split_dispute_escrow(
+        &ctx.accounts.job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &[
+            &ctx.accounts.driver_infra_stable,
+            &ctx.accounts.customer_infra_stable,
+            &ctx.accounts.country_stable_account,
+            &ctx.accounts.global_stable_account,
+        ],
+        driver_award_basis_point,
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+    record_dispute_outcome(
+        dispute,
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+        driver_award_basis_point,
+        current_time,
+    );
+
+    Ok(())
+}
+
+// Raiser wins by default once the counterparty stayed silent for the whole window
+pub fn process_claim_default_judgment(
+    ctx: Context<ClaimDefaultJudgment>,
+    job_count: u64,
+) -> Result<()> {
+    let dispute = &mut ctx.accounts.dispute;
+
+    if dispute.resolved_time.is_some() {
+        return err!(ErrorCode::DisputeAlreadyResolved);
+    }
+    if dispute.responded_time.is_some() {
+        return err!(ErrorCode::DisputeResponded);
+    }
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time <= dispute.response_deadline {
+        return err!(ErrorCode::DisputeResponseWindowNotOver);
+    }
+
+    let driver_award_basis_point = match dispute.opener {
+        Actor::DriverInfra => MAX_BASIS_POINT as u16,
+        Actor::CustomerInfra => 0,
+    };
+
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let driver_infra_key = ctx.accounts.driver_infra.key();
+    let seeds = &[
+        b"job".as_ref(),
+        driver_infra_key.as_ref(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    split_dispute_escrow(
+        &ctx.accounts.job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &[
+            &ctx.accounts.driver_infra_stable,
+            &ctx.accounts.customer_infra_stable,
+            &ctx.accounts.country_stable_account,
+            &ctx.accounts.global_stable_account,
+        ],
+        driver_award_basis_point,
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+    record_dispute_outcome(
+        dispute,
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+        driver_award_basis_point,
+        current_time,
+    );
+
+    Ok(())
+}
+
+// Pay the platform shares of the job distribution, split the rest between
+// driver and customer infra and close the escrow.
+// Customer infra takes the remainder so escrow is fully drained.
+fn split_dispute_escrow<'info>(
+    job: &Account<'info, Job>,
+    signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    job_esrow_stable: &Account<'info, TokenAccount>,
+    recipients: &[&Account<'info, TokenAccount>],
+    driver_award_basis_point: u16,
+    rent_collector: AccountInfo<'info>,
+) -> Result<()> {
+    let find_recipient = |provider: Pubkey| {
+        recipients
+            .iter()
+            .copied()
+            .find(|item| item.owner == provider)
+            .ok_or(ErrorCode::MissingDistributionAccount)
+    };
+    let mut remaining = job_esrow_stable.amount;
+
+    let platform_shares: Vec<&Distribution> = job
+        .distribution
+        .iter()
+        .filter(|share| share.provider != job.driver_infra && share.provider != job.customer_infra)
+        .collect();
+    let basis_points: Vec<u16> = platform_shares
+        .iter()
+        .map(|share| share.basis_point_payout)
+        .collect();
+    let distributable = job.total_fee_cent.min(remaining);
+    let amounts = fee_math::split_by_basis_point(distributable, &basis_points)?;
+    for (share, amount) in platform_shares.iter().zip(amounts) {
+        transfer_from_escrow(
+            job,
+            signer,
+            token_program,
+            job_esrow_stable,
+            find_recipient(share.provider)?,
+            amount,
+        )?;
+        remaining = fee_math::checked_sub(remaining, amount)?;
+    }
+
+    let driver_award =
+        fee_math::apply_basis_point(remaining, driver_award_basis_point as u64, Rounding::Down)?;
+    let customer_award = fee_math::checked_sub(remaining, driver_award)?;
+    let driver_infra_stable = find_recipient(job.driver_infra)?;
+    let customer_infra_stable = find_recipient(job.customer_infra)?;
+
+    transfer_from_escrow(
+        job,
+        signer,
+        token_program,
+        job_esrow_stable,
+        driver_infra_stable,
+        driver_award,
+    )?;
+    transfer_from_escrow(
+        job,
+        signer,
+        token_program,
+        job_esrow_stable,
+        customer_infra_stable,
+        customer_award,
+    )?;
+
+    close_job_escrow(job, signer, token_program, job_esrow_stable, rent_collector)
+}
+
+// Job is closed by the ruling, party awarded less than half of the escrow lost the dispute
+fn record_dispute_outcome(
+    dispute: &mut Dispute,
+    driver_infra: &mut DriverInfra,
+    customer_infra: &mut CustomerInfra,
+    driver_award_basis_point: u16,
+    current_time: u64,
+) {
+    release_open_job(driver_infra, customer_infra);
+    driver_infra.dispute_cases += 1;
+    customer_infra.dispute_cases += 1;
+    if (driver_award_basis_point as u64) * 2 < MAX_BASIS_POINT {
+        driver_infra.cases_lost_in_dispute += 1;
+    } else if (driver_award_basis_point as u64) * 2 > MAX_BASIS_POINT {
+        customer_infra.cases_lost_in_dispute += 1;
+    }
+    reputation::refresh_driver_infra(driver_infra);
+    reputation::refresh_customer_infra(customer_infra);
+
+    dispute.driver_award_basis_point = Some(driver_award_basis_point);
+    dispute.resolved_time = Some(current_time);
+}
@@ -1,4 +1,10 @@
-use crate::{error::ErrorCode, state::*, *};
+use crate::{
+    error::ErrorCode,
+    fee_math::{self, MAX_BASIS_POINT},
+    reputation,
+    state::*,
+    *,
+};
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
@@ -31,6 +37,15 @@ pub fn process_init_driver_infra(
     driver_infra.cancellation = 0;
     driver_infra.dispute_cases = 0;
     driver_infra.cases_lost_in_dispute = 0;
+    driver_infra.open_jobs = 0;
+    driver_infra.pending_withdrawal_cent = 0;
+    driver_infra.withdrawal_available_time = None;
+    driver_infra.suspension_reason = None;
+    driver_infra.suspended_until = None;
+    driver_infra.completed_ride = 0;
+    driver_infra.reputation_basis_point = MAX_BASIS_POINT as u16;
+    driver_infra.company_review_pending = false;
+    driver_infra.is_underfunded = false;
 
     country_state.driver_infra_counter = country_state.driver_infra_counter.checked_add(1).unwrap();
 
@@ -52,7 +67,8 @@ pub fn process_update_driver_infra_company(
     new_company_info.website = params.website;
 
     driver_infra.company_info_current_count = params.old_company_info_count + 1;
-    driver_infra.is_frozen = true;
+    driver_infra.company_review_pending = true;
+    driver_infra.refresh_frozen();
 
     Ok(())
 }
@@ -65,3 +81,153 @@ pub fn process_update_driver_infra_basis_point(
 
     Ok(())
 }
+
+pub fn process_top_up_driver_infra_deposit(
+    ctx: Context<TopUpDriverInfraDeposit>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.driver_infra_owner_stable.to_account_info(),
+        to: ctx.accounts.driver_infra_stable.to_account_info(),
+        authority: ctx.accounts.driver_infra_owner.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new(cpi_program, cpi_accounts);
+    token::transfer(token_transfer_context, amount)?;
+
+    // A top up back to the country minimum lifts an underfunded freeze
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    if driver_infra.is_underfunded {
+        let country_state = &ctx.accounts.country_state;
+        let required_deposit = reputation::required_deposit(
+            country_state.min_driver_infra_deposit,
+            &country_state.deposit_tiers,
+            driver_infra.reputation_basis_point,
+        )?;
+        ctx.accounts.driver_infra_stable.reload()?;
+        let deposit = reputation::bonded_deposit(
+            ctx.accounts.driver_infra_stable.amount,
+            driver_infra.pending_withdrawal_cent,
+        );
+        if deposit >= required_deposit {
+            driver_infra.is_underfunded = false;
+            driver_infra.refresh_frozen();
+        }
+    }
+
+    Ok(())
+}
+
+pub fn process_request_driver_infra_withdrawal(
+    ctx: Context<RequestDriverInfraWithdrawal>,
+    amount: u64,
+) -> Result<()> {
+    let driver_infra = &mut ctx.accounts.driver_infra;
+
+    if driver_infra.open_jobs > 0 {
+        return err!(ErrorCode::InfraHasOpenJobs);
+    }
+    if driver_infra.withdrawal_available_time.is_some() {
+        return err!(ErrorCode::WithdrawalAlreadyPending);
+    }
+    // Ride earnings land in the same stable account, all of it can be unbonded
+    let withdrawable = reputation::bonded_deposit(
+        ctx.accounts.driver_infra_stable.amount,
+        driver_infra.pending_withdrawal_cent,
+    );
+    if amount == 0 || amount > withdrawable {
+        return err!(ErrorCode::InsufficientInfraDeposit);
+    }
+
+    // Unbonding deposit no longer counts towards the minimum
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    driver_infra.pending_withdrawal_cent = amount;
+    driver_infra.withdrawal_available_time = Some(fee_math::checked_add(
+        current_time,
+        ctx.accounts.country_state.infra_unbonding_period_sec,
+    )?);
+
+    Ok(())
+}
+
+pub fn process_complete_driver_infra_withdrawal(
+    ctx: Context<CompleteDriverInfraWithdrawal>,
+    driver_infra_count: u64,
+) -> Result<()> {
+    let driver_infra = &ctx.accounts.driver_infra;
+
+    let withdrawal_available_time = match driver_infra.withdrawal_available_time {
+        Some(time) => time,
+        None => return err!(ErrorCode::NoPendingWithdrawal),
+    };
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time < withdrawal_available_time {
+        return err!(ErrorCode::UnbondingPeriodNotOver);
+    }
+    if driver_infra.open_jobs > 0 {
+        return err!(ErrorCode::InfraHasOpenJobs);
+    }
+
+    // A slash during unbonding can leave less than requested, top up first
+    let amount = driver_infra.pending_withdrawal_cent;
+    if amount > ctx.accounts.driver_infra_stable.amount {
+        return err!(ErrorCode::InsufficientInfraDeposit);
+    }
+
+    let auth_bump = *ctx.bumps.get("driver_infra").unwrap();
+    let seeds = &[
+        b"driver_infra".as_ref(),
+        ctx.accounts.country_state.alpha3_country_code.as_ref(),
+        &driver_infra_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.driver_infra_stable.to_account_info(),
+        to: ctx.accounts.driver_infra_owner_stable.to_account_info(),
+        authority: ctx.accounts.driver_infra.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(token_transfer_context, amount)?;
+
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    driver_infra.pending_withdrawal_cent = 0;
+    driver_infra.withdrawal_available_time = None;
+
+    Ok(())
+}
+
+// Permissionless, freeze once the top up grace period after a minimum deposit raise is over
+pub fn process_freeze_underfunded_driver_infra(
+    ctx: Context<FreezeUnderfundedDriverInfra>,
+) -> Result<()> {
+    let country_state = &ctx.accounts.country_state;
+    let driver_infra = &mut ctx.accounts.driver_infra;
+
+    let required_deposit = reputation::required_deposit(
+        country_state.min_driver_infra_deposit,
+        &country_state.deposit_tiers,
+        driver_infra.reputation_basis_point,
+    )?;
+    let deposit = reputation::bonded_deposit(
+        ctx.accounts.driver_infra_stable.amount,
+        driver_infra.pending_withdrawal_cent,
+    );
+    if deposit >= required_deposit {
+        return err!(ErrorCode::InfraDepositSufficient);
+    }
+    let grace_period_end = fee_math::checked_add(
+        country_state.min_deposit_raised_time,
+        country_state.deposit_grace_period_sec,
+    )?;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time <= grace_period_end {
+        return err!(ErrorCode::DepositGracePeriodNotOver);
+    }
+
+    driver_infra.is_underfunded = true;
+    driver_infra.refresh_frozen();
+
+    Ok(())
+}
@@ -1,4 +1,13 @@
-use crate::{error::ErrorCode, state::*, *};
+use crate::{
+    cancellation,
+    error::ErrorCode,
+    fare::{self, FareAdjustment},
+    fee_math,
+    lifecycle::Actor,
+    reputation,
+    state::*,
+    *,
+};
 
 use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
@@ -13,18 +22,31 @@ pub fn process_driver_start_work(
 ) -> Result<()> {
     let driver = &mut ctx.accounts.driver;
 
-    let valid_lat = is_valid_coordinate(current_location.lat);
-    let valid_long = is_valid_coordinate(current_location.long);
-
-    if !valid_lat && !valid_long {
-        return err!(ErrorCode::InvalidCoordinatesValid);
-    }
+    current_location.validate()?;
 
     if driver.is_initialized {
         return err!(ErrorCode::DriverAlreadyInitialized);
     }
 
+    validate_driver_catalog(
+        ctx.program_id,
+        ctx.remaining_accounts,
+        ctx.accounts.country_state.key(),
+        &services,
+        &passenger_types,
+    )?;
+
+    // Driver cannot go online with an expired license or credential
+    let driver_profile = &ctx.accounts.driver_profile;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if driver_profile.license_expiry <= current_time
+        || driver_profile.credential_expiry <= current_time
+    {
+        return err!(ErrorCode::DriverCredentialExpired);
+    }
+
     driver.is_initialized = true;
+    driver.profile = driver_profile.key();
     driver.driver_uuid = driver_uuid;
     driver.rsa_pem_pubkey = rsa_pem_pubkey;
     driver.infra_authority = ctx.accounts.driver_infra.key();
@@ -36,6 +58,97 @@ pub fn process_driver_start_work(
     driver.vehicle = ctx.accounts.vehicle.key();
     driver.number_of_seats = ctx.accounts.vehicle.number_of_seats;
     driver.location_update_authority = ctx.accounts.location_update_authority.key();
+    driver.availability = DriverAvailability::Online;
+    driver.location_last_update = Clock::get().unwrap().unix_timestamp as u64;
+
+    Ok(())
+}
+
+// Remaining accounts are the OfferedService accounts for `services` followed by
+// the PassengerTypes accounts for `passenger_types`, in the same order as the IDs
+fn validate_driver_catalog(
+    program_id: &Pubkey,
+    remaining_accounts: &[AccountInfo],
+    country_key: Pubkey,
+    services: &[u64],
+    passenger_types: &[u64],
+) -> Result<()> {
+    if remaining_accounts.len() != services.len() + passenger_types.len() {
+        return err!(ErrorCode::InvalidCatalogAccount);
+    }
+    if has_duplicate(services) || has_duplicate(passenger_types) {
+        return err!(ErrorCode::DuplicateCatalogItem);
+    }
+    let (service_accounts, passenger_type_accounts) = remaining_accounts.split_at(services.len());
+
+    for (service_count, account) in services.iter().zip(service_accounts) {
+        let seeds: &[&[u8]] = &[b"offered_service".as_ref(), &service_count.to_le_bytes()];
+        let service = load_catalog_account::<OfferedService>(program_id, seeds, account)?;
+        if service.country_key != country_key {
+            return err!(ErrorCode::CatalogItemWrongCountry);
+        }
+        if !service.is_valid {
+            return err!(ErrorCode::InvalidCatalogItem);
+        }
+    }
+    for (passenger_type_count, account) in passenger_types.iter().zip(passenger_type_accounts) {
+        let seeds: &[&[u8]] = &[
+            b"passenger_type".as_ref(),
+            &passenger_type_count.to_le_bytes(),
+        ];
+        let passenger_type = load_catalog_account::<PassengerTypes>(program_id, seeds, account)?;
+        if !passenger_type.is_valid {
+            return err!(ErrorCode::InvalidCatalogItem);
+        }
+    }
+    Ok(())
+}
+
+fn load_catalog_account<T: AccountDeserialize>(
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+    account: &AccountInfo,
+) -> Result<T> {
+    let (expected_key, _bump) = Pubkey::find_program_address(seeds, program_id);
+    if account.key() != expected_key || account.owner != program_id {
+        return err!(ErrorCode::InvalidCatalogAccount);
+    }
+    let data = account.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+fn has_duplicate(ids: &[u64]) -> bool {
+    ids.iter()
+        .enumerate()
+        .any(|(index, id)| ids[..index].contains(id))
+}
+
+pub fn process_init_driver_profile(
+    ctx: Context<InitDriverProfile>,
+    driver_uuid: String,
+    license_expiry: u64,
+    credential_expiry: u64,
+) -> Result<()> {
+    let driver_profile = &mut ctx.accounts.driver_profile;
+
+    driver_profile.driver_infra = ctx.accounts.driver_infra.key();
+    driver_profile.driver_uuid = driver_uuid;
+    driver_profile.license_expiry = license_expiry;
+    driver_profile.credential_expiry = credential_expiry;
+    driver_profile.created_time = Clock::get().unwrap().unix_timestamp as u64;
+    driver_profile.quote_nonce = 0;
+
+    Ok(())
+}
+
+pub fn process_update_driver_profile_credential(
+    ctx: Context<UpdateDriverProfileCredential>,
+    license_expiry: u64,
+    credential_expiry: u64,
+) -> Result<()> {
+    let driver_profile = &mut ctx.accounts.driver_profile;
+    driver_profile.license_expiry = license_expiry;
+    driver_profile.credential_expiry = credential_expiry;
 
     Ok(())
 }
@@ -47,23 +160,29 @@ pub fn process_driver_update_location(
 ) -> Result<()> {
     let driver = &mut ctx.accounts.driver;
 
-    let valid_lat = is_valid_coordinate(current_location.lat);
-    let valid_long = is_valid_coordinate(current_location.long);
-
-    if !valid_lat && !valid_long {
-        return err!(ErrorCode::InvalidCoordinatesValid);
-    }
+    current_location.validate()?;
 
+    // Next destination only moves while the driver is on a job
     if driver.next_location.is_some() {
-        let valid_next_lat = is_valid_coordinate(next_location.clone().unwrap().lat);
-        let valid_next_long = is_valid_coordinate(next_location.clone().unwrap().long);
-        if !valid_next_lat && !valid_next_long {
-            return err!(ErrorCode::InvalidCoordinatesValid);
+        if let Some(next_location) = next_location {
+            next_location.validate()?;
+            driver.next_location = Some(next_location);
         }
-        driver.next_location = next_location;
     }
 
     driver.last_location = current_location;
+    driver.location_last_update = Clock::get().unwrap().unix_timestamp as u64;
+
+    Ok(())
+}
+
+pub fn process_driver_set_availability(
+    ctx: Context<DriverSetAvailability>,
+    availability: DriverAvailability,
+) -> Result<()> {
+    let driver = &mut ctx.accounts.driver;
+    driver.availability.check_manual_change(availability)?;
+    driver.availability = availability;
 
     Ok(())
 }
@@ -76,100 +195,165 @@ pub fn process_driver_end_work(ctx: Context<DriverEndWork>) -> Result<()> {
     Ok(())
 }
 
-pub fn process_driver_complete_job(ctx: Context<DriverCompleteJob>, job_count: u64) -> Result<()> {
-    let customer_infra = &mut ctx.accounts.customer_infra;
-    let driver_infra = &mut ctx.accounts.driver_infra;
+// Permissionless, takes a driver that lost connectivity offline and reclaims
+// the session rent when no job still holds the driver
+pub fn process_mark_stale_driver(ctx: Context<MarkStaleDriver>) -> Result<()> {
     let driver = &mut ctx.accounts.driver;
-    let job = &mut ctx.accounts.job;
-    let country_state = &mut ctx.accounts.country_state;
+    let heartbeat_timeout_sec = ctx.accounts.country_state.driver_heartbeat_timeout_sec;
 
-    if job.status == Status::Init {
-        return err!(ErrorCode::JobNotYetStarted);
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if !driver.is_stale(current_time, heartbeat_timeout_sec)? {
+        return err!(ErrorCode::DriverNotStale);
     }
 
-    // This occur when dispute status is marked as completed before ride ended
-    if job.status == Status::DisputeByCustomer {
-        return err!(ErrorCode::JobDisputeByCustomer);
-    }
-    if job.status == Status::CancelledByCustomer {
-        return err!(ErrorCode::CancelledByCustomer);
-    }
-    if job.status == Status::CancelledByDriver {
-        return err!(ErrorCode::CancelledByDriver);
+    driver.availability = DriverAvailability::Offline;
+    driver.clear_reservation();
+
+    if driver.active_job.is_none() {
+        driver.is_initialized = false;
+        driver.next_location = None;
+        driver.close(ctx.accounts.driver_infra_owner.to_account_info())?;
     }
 
+    Ok(())
+}
+
+pub fn process_driver_complete_job(
+    ctx: Context<DriverCompleteJob>,
+    customer_infra_count: u64,
+    job_count: u64,
+) -> Result<()> {
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    let driver_infra = &mut ctx.accounts.driver_infra;
+    let driver = &mut ctx.accounts.driver;
+    let job = &mut ctx.accounts.job;
+    let country_state = &mut ctx.accounts.country_state;
+
     // End trip without funds approval, update status to complete
     // Only run the first time
+    let current_time_sec = Clock::get().unwrap().unix_timestamp as u64;
     if job.status != Status::Completed {
-        job.job_end_time = Some(Clock::get().unwrap().unix_timestamp as u64);
+        job.transition(Status::Completed, Actor::DriverInfra, current_time_sec)?;
+        driver_infra.completed_ride += 1;
+        customer_infra.completed_ride += 1;
+        ctx.accounts.driver_profile.completed_job += 1;
+        driver.release_from_job(job.key());
+
+        // Rate card on the agreed distance and the time with the customer on board,
+        // waiting before pickup is billed separately
+        let trip_duration_sec =
+            fee_math::checked_sub(job.job_end_time.unwrap(), job.pickup_time.unwrap())?;
+        let final_fare = fare::compute_fare(
+            job.terms.base_rate_cent,
+            job.terms.min_km_rate_cent,
+            job.terms.min_min_fee_cent,
+            job.trip_distance_m,
+            trip_duration_sec,
+        )?;
+        match fare::fare_adjustment(job.total_fee_cent, final_fare) {
+            FareAdjustment::TopUp(amount) => {
+                if ctx.accounts.customer_infra_stable.amount < amount {
+                    return err!(ErrorCode::InsufficientFareTopUp);
+                }
+                let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
+                let seeds = &[
+                    b"customer_infra".as_ref(),
+                    country_state.alpha3_country_code.as_ref(),
+                    &customer_infra_count.to_le_bytes(),
+                    &[auth_bump],
+                ];
+                let signer = &[&seeds[..]];
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.customer_infra_stable.to_account_info(),
+                    to: ctx.accounts.job_esrow_stable.to_account_info(),
+                    authority: customer_infra.to_account_info(),
+                };
+                let token_transfer_context =
+                    CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+                token::transfer(token_transfer_context, amount)?;
+                job.total_fee_cent = fee_math::checked_add(job.total_fee_cent, amount)?;
+            }
+            FareAdjustment::Refund(amount) => {
+                let auth_bump = *ctx.bumps.get("job").unwrap();
+                let seeds = &[
+                    b"job".as_ref(),
+                    &driver_infra.key().to_bytes(),
+                    &job_count.to_le_bytes(),
+                    &[auth_bump],
+                ];
+                let signer = &[&seeds[..]];
+                transfer_from_escrow(
+                    job,
+                    signer,
+                    &ctx.accounts.token_program,
+                    &ctx.accounts.job_esrow_stable,
+                    &ctx.accounts.customer_infra_stable,
+                    amount,
+                )?;
+                job.total_fee_cent = fee_math::checked_sub(job.total_fee_cent, amount)?;
+            }
+            FareAdjustment::None => {}
+        }
+        return Ok(());
     }
 
     // status complete & finalize_duration_sec over, approve fund
-    let current_time_sec = Clock::get().unwrap().unix_timestamp as u64;
-    let valid_claim_time = job.job_end_time.unwrap() + country_state.finalize_duration_sec;
+    let valid_claim_time =
+        fee_math::checked_add(job.job_end_time.unwrap(), job.terms.finalize_duration_sec)?;
+    if current_time_sec <= valid_claim_time {
+        return err!(ErrorCode::FinalizeDurationNotOver);
+    }
 
-    if job.status == Status::Completed && current_time_sec > valid_claim_time {
-        // Transfer funds from escrow to driver_infra_owner and customer_infra_owner
-        let auth_bump = *ctx.bumps.get("job").unwrap();
-        let seeds = &[
-            b"job".as_ref(),
-            &driver_infra.key().to_bytes(),
-            &job_count.to_le_bytes(),
-            &[auth_bump],
-        ];
-        let signer = &[&seeds[..]];
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_accounts_to_driver_infra = Transfer {
-            from: ctx.accounts.job_esrow_stable.to_account_info(),
-            to: ctx.accounts.driver_infra_stable.to_account_info(),
-            authority: job.to_account_info(),
-        };
-        let token_transfer_context_to_driver_infra =
-            CpiContext::new_with_signer(cpi_program.clone(), cpi_accounts_to_driver_infra, signer);
+    // Transfer funds from escrow to driver_infra_owner and customer_infra_owner
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let seeds = &[
+        b"job".as_ref(),
+        &driver_infra.key().to_bytes(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    settle_job_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &[
+            &ctx.accounts.driver_infra_stable,
+            &ctx.accounts.customer_infra_stable,
+            &ctx.accounts.country_stable_account,
+            &ctx.accounts.global_stable_account,
+        ],
+        None,
+        customer_infra.to_account_info(),
+    )?;
+
+    release_open_job(
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+    );
 
-        let cpi_accounts_to_customer_infra = Transfer {
-            from: ctx.accounts.job_esrow_stable.to_account_info(),
-            to: ctx.accounts.customer_infra_stable.to_account_info(),
-            authority: job.to_account_info(),
-        };
-        let token_transfer_context_to_customer_infra =
-            CpiContext::new_with_signer(cpi_program, cpi_accounts_to_customer_infra, signer);
-
-        let driver_distribution = job
-            .distribution
-            .iter()
-            .find(|item| item.provider == driver_infra.key())
-            .unwrap();
-
-        let customer_distribution = job
-            .distribution
-            .iter()
-            .find(|item| item.provider == customer_infra.key())
-            .unwrap();
-
-        let amount_to_driver =
-            job.total_fee_cent / 10_000 * driver_distribution.basis_point_payout as u64;
-        let amount_to_customer =
-            job.total_fee_cent / 10_000 * customer_distribution.basis_point_payout as u64;
-
-        token::transfer(token_transfer_context_to_driver_infra, amount_to_driver)?;
-        token::transfer(token_transfer_context_to_customer_infra, amount_to_customer)?;
-
-        // Close job account
-        job.job_end_time = None;
-        job.total_fee_cent = 0;
-        job.distribution = vec![];
-        job.encrypted_data = "".to_owned();
-        job.encrypted_combined_rand_base64 = "".to_owned();
-        job.close(ctx.accounts.customer_infra.to_account_info())?;
-
-        // Close driver account
-        driver.driver_uuid = "".to_owned();
-        driver.is_initialized = false;
-        driver.next_location = None;
-        driver.close(ctx.accounts.driver_infra.to_account_info())?;
-    }
-    job.status = Status::Completed;
+    // Close job account
+    job.job_end_time = None;
+    job.total_fee_cent = 0;
+    job.distribution = vec![];
+    job.encrypted_data = "".to_owned();
+    job.encrypted_combined_rand_base64 = "".to_owned();
+    job.close(ctx.accounts.customer_infra.to_account_info())?;
+
+    // Driver session stays open for the next job
+    driver.next_location = None;
+
+    Ok(())
+}
+
+pub fn process_driver_arrived(ctx: Context<DriverArrived>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    // Waiting fees are computed from arrival_time on pickup
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    job.transition(Status::Arrived, Actor::DriverInfra, current_time)?;
 
     Ok(())
 }
@@ -181,26 +365,24 @@ pub fn process_driver_pickup_customer(
     let job = &mut ctx.accounts.job;
     let country_state = &mut ctx.accounts.country_state;
 
-    if job.status == Status::CancelledByCustomer {
-        return err!(ErrorCode::CancelledByCustomer);
-    }
-    if job.status == Status::DisputeByCustomer {
-        return err!(ErrorCode::JobDisputeByCustomer);
-    }
-    if job.status != Status::Arrived {
-        return err!(ErrorCode::IncorrectLifecycleArrived);
-    }
-
-    job.status = Status::Started;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    job.transition(Status::Started, Actor::DriverInfra, current_time)?;
 
     // WAITING FEES
-    let current_time = Clock::get().unwrap().unix_timestamp as u64;
-    if current_time - country_state.waiting_fee_sec > job.arrival_time.unwrap() {
+    let waited_sec = current_time.saturating_sub(job.arrival_time.unwrap());
+    job.waiting_fee_cent = fare::waiting_fee(
+        waited_sec,
+        job.terms.waiting_fee_sec,
+        job.terms.waiting_fee_cent,
+        job.terms.max_waiting_fee_cent,
+    )?;
+    if job.waiting_fee_cent > 0 {
         // Process to charge customer_infra waiting time
         // Transfer fees to driver_infra
         let auth_bump = *ctx.bumps.get("customer_infra").unwrap();
         let seeds = &[
             b"customer_infra".as_ref(),
+            country_state.alpha3_country_code.as_ref(),
             &customer_infra_count.to_le_bytes(),
             &[auth_bump],
         ];
@@ -213,12 +395,80 @@ pub fn process_driver_pickup_customer(
         };
         let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(token_transfer_context, country_state.waiting_fee_cent)?;
+        token::transfer(token_transfer_context, job.waiting_fee_cent)?;
     }
 
     Ok(())
 }
 
+pub fn process_driver_report_no_show(
+    ctx: Context<DriverReportNoShow>,
+    job_count: u64,
+) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    // Customer gets the agreed no-show wait from arrival
+    if let Some(arrival_time) = job.arrival_time {
+        let no_show_time = fee_math::checked_add(arrival_time, job.terms.no_show_wait_sec)?;
+        if current_time <= no_show_time {
+            return err!(ErrorCode::NoShowWaitNotPassed);
+        }
+    }
+    job.transition(Status::CustomerNoShow, Actor::DriverInfra, current_time)?;
+
+    // No-show fee to driver_infra, the rest of the escrow back to customer_infra
+    let escrowed = ctx.accounts.job_esrow_stable.amount;
+    let no_show_fee = job.terms.no_show_fee_cent.min(escrowed);
+    let refund = fee_math::checked_sub(escrowed, no_show_fee)?;
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let seeds = &[
+        b"job".as_ref(),
+        &ctx.accounts.driver_infra.key().to_bytes(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    transfer_from_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &ctx.accounts.driver_infra_stable,
+        no_show_fee,
+    )?;
+    transfer_from_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &ctx.accounts.customer_infra_stable,
+        refund,
+    )?;
+    close_job_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+
+    // A no-show counts against the customer infra like a cancellation
+    let customer_infra = &mut ctx.accounts.customer_infra;
+    customer_infra.cancellation += 1;
+    reputation::refresh_customer_infra(customer_infra);
+    ctx.accounts.driver.release_from_job(job.key());
+    release_open_job(
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+    );
+
+    // Close job account
+    job.close(ctx.accounts.customer_infra.to_account_info())?;
+
+    Ok(())
+}
+
 pub fn process_driver_cancel_job(
     ctx: Context<DriverCancelJob>,
     driver_infra_count: u64,
@@ -228,69 +478,95 @@ pub fn process_driver_cancel_job(
     let job = &mut ctx.accounts.job;
     let country_state = &mut ctx.accounts.country_state;
 
-    if job.status == Status::CancelledByCustomer {
-        return err!(ErrorCode::CancelledByCustomer);
-    }
-    if job.status == Status::DisputeByCustomer {
-        return err!(ErrorCode::JobDisputeByCustomer);
-    }
-
     // Lifecycle Requirement
-    if job.status == Status::JobAccepted || job.status == Status::Init {
-        job.status = Status::RejectedByDriver;
-
-        if job.status == Status::JobAccepted {
-            job.status = Status::CancelledByDriver;
-
-            // Charge driver_infra if driver cancel job after driver_cancellation_fee_sec
-            let current_time = Clock::get().unwrap().unix_timestamp as u64;
-            if current_time - country_state.driver_cancellation_fee_sec
-                > job.job_start_time.unwrap()
-            {
-                // Process to transfer driver_infra to customer_infra
-                let auth_bump = *ctx.bumps.get("driver_infra").unwrap();
-                let seeds = &[
-                    b"driver_infra".as_ref(),
-                    &driver_infra_count.to_le_bytes(),
-                    &[auth_bump],
-                ];
-                let signer = &[&seeds[..]];
-                let cpi_program = ctx.accounts.token_program.to_account_info();
-                let cpi_accounts = Transfer {
-                    from: ctx.accounts.driver_infra_stable.to_account_info(),
-                    to: ctx.accounts.customer_infra_stable.to_account_info(),
-                    authority: ctx.accounts.driver_infra.to_account_info(),
-                };
-                let token_transfer_context =
-                    CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    // Not yet accepted is a rejection, otherwise a cancellation
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let next_status = if job.status == Status::Init {
+        Status::RejectedByDriver
+    } else {
+        Status::CancelledByDriver
+    };
+    // Cancellation schedule agreed at request, evaluated on the stage being left
+    let cancellation_tier = cancellation::applicable_tier(
+        &job.terms.cancellation_tiers,
+        Actor::DriverInfra,
+        job,
+        current_time,
+    );
+    job.transition(next_status, Actor::DriverInfra, current_time)?;
+
+    // Driver tiers always charge driver_infra
+    let fee = match cancellation_tier {
+        Some(tier) => tier.fee(job.total_fee_cent)?,
+        None => 0,
+    };
+    job.applied_cancellation_tier = cancellation_tier;
+    emit!(JobCancelled {
+        job: job.key(),
+        status: job.status,
+        tier: cancellation_tier,
+        fee_cent: fee,
+    });
+    if fee > 0 {
+        // Process to transfer driver_infra to customer_infra
+        let auth_bump = *ctx.bumps.get("driver_infra").unwrap();
+        let seeds = &[
+            b"driver_infra".as_ref(),
+            country_state.alpha3_country_code.as_ref(),
+            &driver_infra_count.to_le_bytes(),
+            &[auth_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.driver_infra_stable.to_account_info(),
+            to: ctx.accounts.customer_infra_stable.to_account_info(),
+            authority: ctx.accounts.driver_infra.to_account_info(),
+        };
+        let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-                token::transfer(token_transfer_context, country_state.cancellation_fee_cent)?;
-            }
+        token::transfer(token_transfer_context, fee)?;
+    }
 
-            // Transfer funds back to customer_infra
-            let auth_bump = *ctx.bumps.get("job").unwrap();
-            let seeds = &[
-                b"job".as_ref(),
-                &ctx.accounts.driver_infra.key().to_bytes(),
-                &job_count.to_le_bytes(),
-                &[auth_bump],
-            ];
-            let signer = &[&seeds[..]];
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.job_esrow_stable.to_account_info(),
-                to: ctx.accounts.customer_infra_stable.to_account_info(),
-                authority: job.to_account_info(),
-            };
-            let token_transfer_context =
-                CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-
-            token::transfer(token_transfer_context, job.total_fee_cent)?;
-        }
-    } else {
-        msg!("job.status, {:?}", job.status);
-        return err!(ErrorCode::IncorrectLifecycleJobAccepted);
+    // Transfer funds back to customer_infra
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let seeds = &[
+        b"job".as_ref(),
+        &ctx.accounts.driver_infra.key().to_bytes(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.job_esrow_stable.to_account_info(),
+        to: ctx.accounts.customer_infra_stable.to_account_info(),
+        authority: job.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+    token::transfer(token_transfer_context, job.total_fee_cent)?;
+    close_job_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+
+    if next_status == Status::CancelledByDriver {
+        let driver_infra = &mut ctx.accounts.driver_infra;
+        driver_infra.cancellation += 1;
+        reputation::refresh_driver_infra(driver_infra);
+        ctx.accounts.driver_profile.cancelled_job += 1;
     }
+    // Rejected or cancelled, the job no longer holds the driver
+    driver.release_from_job(job.key());
+
+    release_open_job(
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+    );
 
     // Close job account
     job.job_end_time = None;
@@ -301,26 +577,51 @@ pub fn process_driver_cancel_job(
     job.encrypted_combined_rand_base64 = "".to_owned();
     job.close(ctx.accounts.customer_infra.to_account_info())?;
 
-    // Close driver account
-    driver.driver_uuid = "".to_owned();
-    driver.is_initialized = false;
+    // Driver session stays open for the next job
     driver.next_location = None;
-    driver.close(ctx.accounts.driver_infra.to_account_info())?;
 
     Ok(())
 }
 
-pub fn process_driver_raise_issue(ctx: Context<DriverRaiseIssue>) -> Result<()> {
+pub fn process_driver_raise_issue(
+    ctx: Context<DriverRaiseIssue>,
+    reason: DisputeReason,
+    evidence_hash: [u8; 32],
+) -> Result<()> {
     let job = &mut ctx.accounts.job;
 
-    if job.status == Status::CancelledByCustomer {
-        return err!(ErrorCode::CancelledByCustomer);
-    }
-    if job.status == Status::DisputeByCustomer {
-        return err!(ErrorCode::JobDisputeByCustomer);
-    }
-
-    job.status = Status::DisputeByDriver;
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let in_progress = job.status != Status::Completed;
+    job.transition(Status::DisputeByDriver, Actor::DriverInfra, current_time)?;
+    release_disputed_driver(&mut ctx.accounts.driver, job.key(), in_progress)?;
+
+    open_dispute(
+        &mut ctx.accounts.dispute,
+        job.key(),
+        Actor::DriverInfra,
+        reason,
+        evidence_hash,
+        current_time,
+        job.terms.dispute_waitout_period,
+    )?;
 
     Ok(())
 }
+
+// A job disputed before completion no longer holds its driver
+pub fn release_disputed_driver(
+    driver: &mut Option<Account<Driver>>,
+    job: Pubkey,
+    in_progress: bool,
+) -> Result<()> {
+    if !in_progress {
+        return Ok(());
+    }
+    match driver {
+        Some(driver) => {
+            driver.release_from_job(job);
+            Ok(())
+        }
+        None => err!(ErrorCode::DriverAccountRequired),
+    }
+}
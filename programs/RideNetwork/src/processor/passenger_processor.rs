@@ -17,6 +17,7 @@ pub fn process_init_or_update_passenger(
     }
     passenger_type.name = passenger_type_name;
     passenger_type.is_valid = false;
+    passenger_type.is_rejected = false;
 
     // TRANSFER FEES TO ESCROW
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -33,3 +34,55 @@ pub fn process_init_or_update_passenger(
 
     Ok(())
 }
+
+pub fn process_approve_passenger_type(
+    ctx: Context<PassengerTypeVerification>,
+    passenger_type_count: u64,
+) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("passenger_type").unwrap();
+    let seeds = &[
+        b"passenger_type".as_ref(),
+        &passenger_type_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee goes to the global treasury
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.passenger_type_escrow_account,
+        &ctx.accounts.global_token_account,
+        ctx.accounts.passenger_type.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.passenger_type.is_valid = true;
+
+    Ok(())
+}
+
+pub fn process_reject_passenger_type(
+    ctx: Context<PassengerTypeVerification>,
+    passenger_type_count: u64,
+) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("passenger_type").unwrap();
+    let seeds = &[
+        b"passenger_type".as_ref(),
+        &passenger_type_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee is refunded, passenger type stays invalid and is marked rejected
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.passenger_type_escrow_account,
+        &ctx.accounts.initializer_token_account,
+        ctx.accounts.passenger_type.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.passenger_type.is_rejected = true;
+
+    Ok(())
+}
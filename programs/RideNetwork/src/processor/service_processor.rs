@@ -1,7 +1,7 @@
 use crate::error::ErrorCode;
 use crate::*;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, transfer, Transfer};
+use anchor_spl::token::{self, transfer, CloseAccount, Token, TokenAccount, Transfer};
 
 pub fn process_init_or_update_service(
     ctx: Context<InitOrUpdateService>,
@@ -20,6 +20,7 @@ pub fn process_init_or_update_service(
     }
     service_type.name = service_type_name;
     service_type.is_valid = false;
+    service_type.is_rejected = false;
 
     // TRANSFER FEES TO ESCROW
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -36,3 +37,86 @@ pub fn process_init_or_update_service(
 
     Ok(())
 }
+
+pub fn process_approve_service(
+    ctx: Context<ServiceVerification>,
+    service_type_count: u64,
+) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("service_type").unwrap();
+    let seeds = &[
+        b"offered_service".as_ref(),
+        &service_type_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee goes to the country treasury
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.service_type_escrow_account,
+        &ctx.accounts.country_token_account,
+        ctx.accounts.service_type.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.service_type.is_valid = true;
+
+    Ok(())
+}
+
+pub fn process_reject_service(
+    ctx: Context<ServiceVerification>,
+    service_type_count: u64,
+) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("service_type").unwrap();
+    let seeds = &[
+        b"offered_service".as_ref(),
+        &service_type_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee is refunded, service stays invalid and is marked rejected
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.service_type_escrow_account,
+        &ctx.accounts.initializer_token_account,
+        ctx.accounts.service_type.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.service_type.is_rejected = true;
+
+    Ok(())
+}
+
+// Move the whole listing fee out of a catalog item escrow and close it,
+// the escrow rent goes back to the initializer
+pub fn release_catalog_escrow<'info>(
+    signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    escrow: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    item: AccountInfo<'info>,
+    initializer: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: escrow.to_account_info(),
+        to: to.to_account_info(),
+        authority: item.clone(),
+    };
+    let token_transfer_context =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    transfer(token_transfer_context, escrow.amount)?;
+
+    let cpi_accounts = CloseAccount {
+        account: escrow.to_account_info(),
+        destination: initializer,
+        authority: item,
+    };
+    let close_context =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::close_account(close_context)?;
+
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use crate::{
+    error::ErrorCode,
+    fee_math::{self, MAX_BASIS_POINT},
+    state::*,
+    *,
+};
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+pub fn process_settle_job(ctx: Context<SettleJob>, job_count: u64) -> Result<()> {
+    let job = &ctx.accounts.job;
+
+    // Only undisputed jobs past the finalize duration can be settled
+    if job.status != Status::Completed {
+        return err!(ErrorCode::IncorrectLifecycleCompleted);
+    }
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    let valid_claim_time =
+        fee_math::checked_add(job.job_end_time.unwrap(), job.terms.finalize_duration_sec)?;
+    if current_time <= valid_claim_time {
+        return err!(ErrorCode::FinalizeDurationNotOver);
+    }
+
+    let auth_bump = *ctx.bumps.get("job").unwrap();
+    let driver_infra_key = ctx.accounts.driver_infra.key();
+    let seeds = &[
+        b"job".as_ref(),
+        driver_infra_key.as_ref(),
+        &job_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Keeper is paid first, capped at the escrow balance
+    let keeper_reward = job
+        .terms
+        .keeper_reward_cent
+        .min(ctx.accounts.job_esrow_stable.amount);
+
+    settle_job_escrow(
+        job,
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.job_esrow_stable,
+        &[
+            &ctx.accounts.driver_infra_stable,
+            &ctx.accounts.customer_infra_stable,
+            &ctx.accounts.country_stable_account,
+            &ctx.accounts.global_stable_account,
+        ],
+        Some((&ctx.accounts.keeper_stable, keeper_reward)),
+        ctx.accounts.customer_infra.to_account_info(),
+    )?;
+    release_open_job(
+        &mut ctx.accounts.driver_infra,
+        &mut ctx.accounts.customer_infra,
+    );
+
+    Ok(())
+}
+
+// Job closed, it no longer blocks either infra from withdrawing deposit
+pub fn release_open_job(driver_infra: &mut DriverInfra, customer_infra: &mut CustomerInfra) {
+    driver_infra.open_jobs = driver_infra.open_jobs.saturating_sub(1);
+    customer_infra.open_jobs = customer_infra.open_jobs.saturating_sub(1);
+}
+
+// Distribution basis points cannot exceed the whole fare
+pub fn validate_distribution(distribution: &[Distribution]) -> Result<()> {
+    let total_basis_point: u64 = distribution
+        .iter()
+        .map(|item| item.basis_point_payout as u64)
+        .sum();
+    if total_basis_point > MAX_BASIS_POINT {
+        return err!(ErrorCode::InvalidDistributionBasisPoint);
+    }
+    Ok(())
+}
+
+// Pay out escrow per job distribution and close the escrow account.
+// Dust and any leftover after distribution goes to the driver infra, so
+// the escrow is always fully drained before it is closed.
+pub fn settle_job_escrow<'info>(
+    job: &Account<'info, Job>,
+    signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    job_esrow_stable: &Account<'info, TokenAccount>,
+    recipients: &[&Account<'info, TokenAccount>],
+    keeper_payout: Option<(&Account<'info, TokenAccount>, u64)>,
+    rent_collector: AccountInfo<'info>,
+) -> Result<()> {
+    let mut remaining = job_esrow_stable.amount;
+
+    if let Some((keeper_stable, keeper_reward)) = keeper_payout {
+        transfer_from_escrow(
+            job,
+            signer,
+            token_program,
+            job_esrow_stable,
+            keeper_stable,
+            keeper_reward,
+        )?;
+        remaining = fee_math::checked_sub(remaining, keeper_reward)?;
+    }
+
+    let distributable = job.total_fee_cent.min(remaining);
+    let basis_points: Vec<u16> = job
+        .distribution
+        .iter()
+        .map(|share| share.basis_point_payout)
+        .collect();
+    let amounts = fee_math::split_by_basis_point(distributable, &basis_points)?;
+
+    for (share, amount) in job.distribution.iter().zip(amounts) {
+        let recipient = recipients
+            .iter()
+            .find(|item| item.owner == share.provider)
+            .ok_or(ErrorCode::MissingDistributionAccount)?;
+
+        transfer_from_escrow(
+            job,
+            signer,
+            token_program,
+            job_esrow_stable,
+            recipient,
+            amount,
+        )?;
+        remaining = fee_math::checked_sub(remaining, amount)?;
+    }
+
+    // Leftover after distribution
+    let driver_infra_stable = recipients
+        .iter()
+        .find(|item| item.owner == job.driver_infra)
+        .ok_or(ErrorCode::MissingDistributionAccount)?;
+    transfer_from_escrow(
+        job,
+        signer,
+        token_program,
+        job_esrow_stable,
+        driver_infra_stable,
+        remaining,
+    )?;
+
+    close_job_escrow(job, signer, token_program, job_esrow_stable, rent_collector)
+}
+
+pub fn close_job_escrow<'info>(
+    job: &Account<'info, Job>,
+    signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    job_esrow_stable: &Account<'info, TokenAccount>,
+    rent_collector: AccountInfo<'info>,
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: job_esrow_stable.to_account_info(),
+        destination: rent_collector,
+        authority: job.to_account_info(),
+    };
+    let close_context =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    token::close_account(close_context)
+}
+
+pub fn transfer_from_escrow<'info>(
+    job: &Account<'info, Job>,
+    signer: &[&[&[u8]]],
+    token_program: &Program<'info, Token>,
+    job_esrow_stable: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let cpi_accounts = Transfer {
+        from: job_esrow_stable.to_account_info(),
+        to: to.to_account_info(),
+        authority: job.to_account_info(),
+    };
+    let token_transfer_context =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+
+    token::transfer(token_transfer_context, amount)
+}
@@ -0,0 +1,124 @@
+use crate::{error::ErrorCode, fee_math, lifecycle::Actor, state::*, *};
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+
+// Record a slash, the infra gets slash_appeal_period_sec to appeal
+#[allow(clippy::too_many_arguments)]
+pub fn record_slash(
+    slash_record: &mut Account<SlashRecord>,
+    country_state: &mut Account<Country>,
+    infra: Pubkey,
+    infra_type: Actor,
+    reason: SlashReason,
+    job: Option<Pubkey>,
+    dispute: Option<Pubkey>,
+    multiplier_basis_point: u64,
+    amount: u64,
+    current_time: u64,
+) -> Result<()> {
+    slash_record.country_key = country_state.key();
+    slash_record.slash_count = country_state.slash_counter;
+    slash_record.infra = infra;
+    slash_record.infra_type = infra_type;
+    slash_record.reason = reason;
+    slash_record.job = job;
+    slash_record.dispute = dispute;
+    slash_record.multiplier_basis_point = multiplier_basis_point;
+    slash_record.amount = amount;
+    slash_record.slashed_time = current_time;
+    slash_record.appeal_deadline =
+        fee_math::checked_add(current_time, country_state.slash_appeal_period_sec)?;
+    slash_record.appealed_time = None;
+    slash_record.reversed_time = None;
+
+    country_state.slash_counter += 1;
+
+    Ok(())
+}
+
+pub fn process_appeal_driver_infra_slash(ctx: Context<AppealDriverInfraSlash>) -> Result<()> {
+    appeal_slash(&mut ctx.accounts.slash_record)
+}
+
+pub fn process_appeal_customer_infra_slash(ctx: Context<AppealCustomerInfraSlash>) -> Result<()> {
+    appeal_slash(&mut ctx.accounts.slash_record)
+}
+
+pub fn process_reverse_driver_infra_slash(ctx: Context<ReverseDriverInfraSlash>) -> Result<()> {
+    let slash_record = &mut ctx.accounts.slash_record;
+    let current_time = check_reversible(slash_record)?;
+
+    // Return funds from the country treasury
+    let alpha3_country_code = ctx.accounts.country_state.alpha3_country_code.clone();
+    let auth_bump = *ctx.bumps.get("country_state").unwrap();
+    let seeds = &[
+        b"country".as_ref(),
+        alpha3_country_code.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.country_stable_account.to_account_info(),
+        to: ctx.accounts.driver_stable_account.to_account_info(),
+        authority: ctx.accounts.country_state.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(token_transfer_context, slash_record.amount)?;
+
+    slash_record.reversed_time = Some(current_time);
+
+    Ok(())
+}
+
+pub fn process_reverse_customer_infra_slash(ctx: Context<ReverseCustomerInfraSlash>) -> Result<()> {
+    let slash_record = &mut ctx.accounts.slash_record;
+    let current_time = check_reversible(slash_record)?;
+
+    // Return funds from the country treasury
+    let alpha3_country_code = ctx.accounts.country_state.alpha3_country_code.clone();
+    let auth_bump = *ctx.bumps.get("country_state").unwrap();
+    let seeds = &[
+        b"country".as_ref(),
+        alpha3_country_code.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.country_stable_account.to_account_info(),
+        to: ctx.accounts.customer_stable_account.to_account_info(),
+        authority: ctx.accounts.country_state.to_account_info(),
+    };
+    let token_transfer_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+    token::transfer(token_transfer_context, slash_record.amount)?;
+
+    slash_record.reversed_time = Some(current_time);
+
+    Ok(())
+}
+
+fn appeal_slash(slash_record: &mut SlashRecord) -> Result<()> {
+    if slash_record.appealed_time.is_some() {
+        return err!(ErrorCode::SlashAlreadyAppealed);
+    }
+    let current_time = Clock::get().unwrap().unix_timestamp as u64;
+    if current_time > slash_record.appeal_deadline {
+        return err!(ErrorCode::SlashAppealWindowOver);
+    }
+    slash_record.appealed_time = Some(current_time);
+
+    Ok(())
+}
+
+// Only an appealed slash can be reversed, and only once
+fn check_reversible(slash_record: &SlashRecord) -> Result<u64> {
+    if slash_record.appealed_time.is_none() {
+        return err!(ErrorCode::SlashNotAppealed);
+    }
+    if slash_record.reversed_time.is_some() {
+        return err!(ErrorCode::SlashAlreadyReversed);
+    }
+    Ok(Clock::get().unwrap().unix_timestamp as u64)
+}
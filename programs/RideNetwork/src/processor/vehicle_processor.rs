@@ -18,6 +18,7 @@ pub fn process_init_vehicle(
     vehicle_state.brand = brand;
     vehicle_state.model = model;
     vehicle_state.is_valid = false;
+    vehicle_state.is_rejected = false;
 
     // TRANSFER FEES TO ESCROW
     let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -34,3 +35,52 @@ pub fn process_init_vehicle(
 
     Ok(())
 }
+
+pub fn process_approve_vehicle(
+    ctx: Context<VehicleVerification>,
+    vehicle_count: u64,
+) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("vehicle").unwrap();
+    let seeds = &[
+        b"vehicle".as_ref(),
+        &vehicle_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee goes to the global treasury
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.vehicle_escrow_account,
+        &ctx.accounts.global_token_account,
+        ctx.accounts.vehicle.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.vehicle.is_valid = true;
+
+    Ok(())
+}
+
+pub fn process_reject_vehicle(ctx: Context<VehicleVerification>, vehicle_count: u64) -> Result<()> {
+    let auth_bump = *ctx.bumps.get("vehicle").unwrap();
+    let seeds = &[
+        b"vehicle".as_ref(),
+        &vehicle_count.to_le_bytes(),
+        &[auth_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    // Listing fee is refunded, vehicle stays invalid and is marked rejected
+    release_catalog_escrow(
+        signer,
+        &ctx.accounts.token_program,
+        &ctx.accounts.vehicle_escrow_account,
+        &ctx.accounts.initializer_token_account,
+        ctx.accounts.vehicle.to_account_info(),
+        ctx.accounts.initializer.to_account_info(),
+    )?;
+    ctx.accounts.vehicle.is_rejected = true;
+
+    Ok(())
+}
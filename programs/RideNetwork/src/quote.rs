@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    hash::hash,
+    instruction::Instruction,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::error::ErrorCode;
+
+// Prefix so a quote signature cannot be replayed as any other message
+pub const QUOTE_DOMAIN: &[u8] = b"ride_network:driver_quote:v1";
+
+// Ed25519 program instruction data layout
+const SIGNATURE_COUNT_LEN: usize = 2; // Count and padding
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+// Instruction index pointing at the ed25519 instruction itself
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+// Price the driver infra agrees to, signed off-chain and verified in the
+// same transaction as the ride request
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct DriverQuote {
+    pub driver_infra: Pubkey,
+    pub driver_uuid: String,
+    pub service: u64,
+    pub fare_cent: u64,
+    pub expiry: u64,
+    pub nonce: u64,
+}
+
+impl DriverQuote {
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = QUOTE_DOMAIN.to_vec();
+        message.extend(self.try_to_vec()?);
+        Ok(message)
+    }
+
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.message()?).to_bytes())
+    }
+}
+
+// The ed25519 instruction right before the current one must have verified a
+// signature by `signer` over the quote
+pub fn verify_quote_signature(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    quote: &DriverQuote,
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions)?;
+    if current_index == 0 {
+        return err!(ErrorCode::InvalidQuoteSignature);
+    }
+    let ed25519_instruction =
+        load_instruction_at_checked(current_index as usize - 1, instructions)?;
+    verify_ed25519_instruction(&ed25519_instruction, signer, &quote.message()?)
+}
+
+// The ed25519 program has already checked the signature, only confirm it was
+// exactly one signature by `signer` over `message`, all inline
+pub fn verify_ed25519_instruction(
+    instruction: &Instruction,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    if instruction.program_id != ed25519_program::ID || !instruction.accounts.is_empty() {
+        return err!(ErrorCode::InvalidQuoteSignature);
+    }
+    let data = &instruction.data;
+    if data.len() < SIGNATURE_COUNT_LEN + SIGNATURE_OFFSETS_LEN || data[0] != 1 {
+        return err!(ErrorCode::InvalidQuoteSignature);
+    }
+
+    let read_offset = |field: usize| {
+        let start = SIGNATURE_COUNT_LEN + field * 2;
+        u16::from_le_bytes([data[start], data[start + 1]])
+    };
+    let signature_instruction_index = read_offset(1);
+    let public_key_offset = read_offset(2) as usize;
+    let public_key_instruction_index = read_offset(3);
+    let message_offset = read_offset(4) as usize;
+    let message_size = read_offset(5) as usize;
+    let message_instruction_index = read_offset(6);
+
+    if signature_instruction_index != CURRENT_INSTRUCTION
+        || public_key_instruction_index != CURRENT_INSTRUCTION
+        || message_instruction_index != CURRENT_INSTRUCTION
+    {
+        return err!(ErrorCode::InvalidQuoteSignature);
+    }
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_LEN)
+        .ok_or(ErrorCode::InvalidQuoteSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidQuoteSignature)?;
+    if public_key != signer.as_ref() || signed_message != message {
+        return err!(ErrorCode::InvalidQuoteSignature);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Same layout as web3.js Ed25519Program.createInstructionWithPublicKey
+    fn ed25519_instruction(signer: &Pubkey, message: &[u8], instruction_index: u16) -> Instruction {
+        let public_key_offset: u16 = 16;
+        let signature_offset = public_key_offset + PUBKEY_LEN as u16;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            instruction_index,
+            public_key_offset,
+            instruction_index,
+            message_offset,
+            message.len() as u16,
+            instruction_index,
+        ] {
+            data.extend(value.to_le_bytes());
+        }
+        data.extend(signer.to_bytes());
+        data.extend([0u8; 64]);
+        data.extend(message);
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    fn quote() -> DriverQuote {
+        DriverQuote {
+            driver_infra: Pubkey::new_unique(),
+            driver_uuid: "driver".to_owned(),
+            service: 0,
+            fare_cent: 4_200,
+            expiry: 1_000,
+            nonce: 1,
+        }
+    }
+
+    #[test]
+    fn accepts_inline_signature_over_the_quote() {
+        let signer = Pubkey::new_unique();
+        let message = quote().message().unwrap();
+        let instruction = ed25519_instruction(&signer, &message, CURRENT_INSTRUCTION);
+
+        assert!(verify_ed25519_instruction(&instruction, &signer, &message).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_signer_fare_or_program() {
+        let signer = Pubkey::new_unique();
+        let message = quote().message().unwrap();
+        let instruction = ed25519_instruction(&signer, &message, CURRENT_INSTRUCTION);
+
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &Pubkey::new_unique(), &message),
+            err!(ErrorCode::InvalidQuoteSignature)
+        );
+        let mut cheaper = quote();
+        cheaper.fare_cent = 1;
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &signer, &cheaper.message().unwrap()),
+            err!(ErrorCode::InvalidQuoteSignature)
+        );
+        let mut other_program = instruction.clone();
+        other_program.program_id = Pubkey::new_unique();
+        assert_eq!(
+            verify_ed25519_instruction(&other_program, &signer, &message),
+            err!(ErrorCode::InvalidQuoteSignature)
+        );
+    }
+
+    #[test]
+    fn rejects_data_borrowed_from_another_instruction() {
+        let signer = Pubkey::new_unique();
+        let message = quote().message().unwrap();
+        let instruction = ed25519_instruction(&signer, &message, 0);
+
+        assert_eq!(
+            verify_ed25519_instruction(&instruction, &signer, &message),
+            err!(ErrorCode::InvalidQuoteSignature)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::fee_math::{self, Rounding, MAX_BASIS_POINT};
+use crate::state::{CustomerInfra, DepositTier, DriverInfra, MAX_DEPOSIT_TIERS};
+
+// Share of matched rides not cancelled by the infra itself
+pub fn completion_rate_basis_point(matched_ride: u64, cancellation: u64) -> u16 {
+    if matched_ride == 0 {
+        return MAX_BASIS_POINT as u16;
+    }
+    let kept = matched_ride.saturating_sub(cancellation);
+    rate_basis_point(kept, matched_ride)
+}
+
+// Share of matched rides that ended in a lost dispute
+pub fn dispute_loss_rate_basis_point(matched_ride: u64, cases_lost_in_dispute: u64) -> u16 {
+    if matched_ride == 0 {
+        return 0;
+    }
+    rate_basis_point(cases_lost_in_dispute.min(matched_ride), matched_ride)
+}
+
+// Completion rate discounted by the dispute loss rate, full score without history
+pub fn reputation_basis_point(
+    matched_ride: u64,
+    cancellation: u64,
+    cases_lost_in_dispute: u64,
+) -> u16 {
+    let completion = completion_rate_basis_point(matched_ride, cancellation) as u64;
+    let loss = dispute_loss_rate_basis_point(matched_ride, cases_lost_in_dispute) as u64;
+    (completion * (MAX_BASIS_POINT - loss) / MAX_BASIS_POINT) as u16
+}
+
+pub fn refresh_driver_infra(driver_infra: &mut DriverInfra) {
+    driver_infra.reputation_basis_point = reputation_basis_point(
+        driver_infra.matched_ride,
+        driver_infra.cancellation,
+        driver_infra.cases_lost_in_dispute,
+    );
+}
+
+pub fn refresh_customer_infra(customer_infra: &mut CustomerInfra) {
+    customer_infra.reputation_basis_point = reputation_basis_point(
+        customer_infra.matched_ride,
+        customer_infra.cancellation,
+        customer_infra.cases_lost_in_dispute,
+    );
+}
+
+// Threshold only applies once the infra has enough matched rides to be scored
+pub fn meets_threshold(
+    reputation_basis_point: u16,
+    matched_ride: u64,
+    min_reputation_basis_point: u16,
+    reputation_min_matched_ride: u64,
+) -> bool {
+    matched_ride < reputation_min_matched_ride
+        || reputation_basis_point >= min_reputation_basis_point
+}
+
+// Base deposit scaled by the largest multiplier among tiers the score falls below
+pub fn required_deposit(
+    base_deposit: u64,
+    deposit_tiers: &[DepositTier],
+    reputation_basis_point: u16,
+) -> Result<u64> {
+    let multiplier = deposit_tiers
+        .iter()
+        .filter(|tier| reputation_basis_point < tier.below_reputation_basis_point)
+        .map(|tier| tier.deposit_multiplier_basis_point as u64)
+        .fold(MAX_BASIS_POINT, u64::max);
+    fee_math::apply_basis_point(base_deposit, multiplier, Rounding::Up)
+}
+
+// Balance actually held as deposit: fees are paid out of the same stable
+// account, and an unbonding withdrawal no longer counts
+pub fn bonded_deposit(stable_amount: u64, pending_withdrawal_cent: u64) -> u64 {
+    stable_amount.saturating_sub(pending_withdrawal_cent)
+}
+
+pub fn validate_deposit_tiers(deposit_tiers: &[DepositTier]) -> Result<()> {
+    if deposit_tiers.len() > MAX_DEPOSIT_TIERS {
+        return err!(ErrorCode::InvalidDepositTiers);
+    }
+    let invalid = deposit_tiers.iter().any(|tier| {
+        tier.below_reputation_basis_point as u64 > MAX_BASIS_POINT
+            || (tier.deposit_multiplier_basis_point as u64) < MAX_BASIS_POINT
+    });
+    if invalid {
+        return err!(ErrorCode::InvalidDepositTiers);
+    }
+    Ok(())
+}
+
+fn rate_basis_point(numerator: u64, denominator: u64) -> u16 {
+    (numerator as u128 * MAX_BASIS_POINT as u128 / denominator as u128) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tier(below: u16, multiplier: u32) -> DepositTier {
+        DepositTier {
+            below_reputation_basis_point: below,
+            deposit_multiplier_basis_point: multiplier,
+        }
+    }
+
+    #[test]
+    fn no_history_is_full_score() {
+        assert_eq!(reputation_basis_point(0, 0, 0), 10_000);
+        assert_eq!(reputation_basis_point(10, 0, 0), 10_000);
+    }
+
+    #[test]
+    fn cancellations_and_lost_disputes_lower_the_score() {
+        assert_eq!(completion_rate_basis_point(10, 1), 9_000);
+        assert_eq!(dispute_loss_rate_basis_point(10, 2), 2_000);
+        // 90% completion, 20% dispute loss
+        assert_eq!(reputation_basis_point(10, 1, 2), 7_200);
+        // Counters beyond matched rides cannot underflow
+        assert_eq!(reputation_basis_point(1, 3, 3), 0);
+    }
+
+    #[test]
+    fn bonded_deposit_excludes_the_pending_withdrawal() {
+        assert_eq!(bonded_deposit(10_000, 2_500), 7_500);
+        // Fees already drained the account below the withdrawal
+        assert_eq!(bonded_deposit(2_000, 2_500), 0);
+    }
+
+    #[test]
+    fn threshold_waits_for_enough_rides() {
+        assert!(meets_threshold(0, 4, 8_000, 5));
+        assert!(!meets_threshold(7_999, 5, 8_000, 5));
+        assert!(meets_threshold(8_000, 5, 8_000, 5));
+    }
+
+    #[test]
+    fn lower_reputation_requires_larger_deposit() {
+        let tiers = [tier(9_000, 15_000), tier(7_000, 20_000)];
+        assert_eq!(required_deposit(1_000, &tiers, 9_500), Ok(1_000));
+        assert_eq!(required_deposit(1_000, &tiers, 8_000), Ok(1_500));
+        assert_eq!(required_deposit(1_000, &tiers, 6_000), Ok(2_000));
+        assert_eq!(required_deposit(1_000, &[], 0), Ok(1_000));
+
+        assert!(validate_deposit_tiers(&tiers).is_ok());
+        assert_eq!(
+            validate_deposit_tiers(&[tier(9_000, 9_000)]),
+            err!(ErrorCode::InvalidDepositTiers)
+        );
+        assert_eq!(
+            validate_deposit_tiers(&[tier(10_001, 15_000)]),
+            err!(ErrorCode::InvalidDepositTiers)
+        );
+    }
+}
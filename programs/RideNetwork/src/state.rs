@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+pub use crate::lifecycle::{Actor, DriverAvailability, Status};
+
 #[account]
 pub struct Global {
     // Account state
@@ -32,16 +34,10 @@ pub struct Country {
     pub customer_infra_counter: u64,
     // Stable mint in the local currency
     pub stable_mint: Pubkey,
-    // duration which waiting fees are enforced
+    // Free waiting time after the driver arrives
     pub waiting_fee_sec: u64,
-    // Waiting fees in local stable mint
+    // Waiting fee per started minute after the free time, in local stable mint
     pub waiting_fee_cent: u64,
-    // duration which driver will be charged for cancellation
-    pub driver_cancellation_fee_sec: u64,
-    // duration which customer will be charged for cancellation
-    pub customer_cancellation_fee_sec: u64,
-    // Cancellation fees in local stable mint
-    pub cancellation_fee_cent: u64,
     // Based rate for each job
     pub base_rate_cent: u64,
     // Minimum per km rate
@@ -58,6 +54,38 @@ pub struct Country {
     pub min_customer_infra_deposit: u64,
     // Base reference value for calculating slashed
     pub base_slash_amount: u64,
+    // Reward paid from escrow to whoever settles a finalized job
+    pub keeper_reward_cent: u64,
+    // Delay before a requested infra deposit withdrawal can be completed
+    pub infra_unbonding_period_sec: u64,
+    // Time given to top up after the minimum deposit is raised
+    pub deposit_grace_period_sec: u64,
+    // When the minimum infra deposit was last raised
+    pub min_deposit_raised_time: u64,
+    // Duration a slashed infra has to appeal
+    pub slash_appeal_period_sec: u64,
+    // Number of slash records created
+    pub slash_counter: u64,
+    // Minimum reputation for an infra to be matched
+    pub min_reputation_basis_point: u16,
+    // Matched rides before the reputation threshold applies
+    pub reputation_min_matched_ride: u64,
+    // Deposit multipliers for infras below a reputation
+    pub deposit_tiers: Vec<DepositTier>,
+    // Longest a customer infra can reserve a driver
+    pub max_reservation_hold_sec: u64,
+    // Drivers without a location update for this long are stale
+    pub driver_heartbeat_timeout_sec: u64,
+    // Most waiting fee charged for a single job
+    pub max_waiting_fee_cent: u64,
+    // Time the driver waits at pickup before reporting a no-show
+    pub no_show_wait_sec: u64,
+    // Paid to the driver infra from escrow when the customer does not show
+    pub no_show_fee_cent: u64,
+    // Cancellation fees by lifecycle stage, elapsed time and who cancels
+    pub cancellation_tiers: Vec<CancellationTier>,
+    // Assumed driver speed to pickup, used for the pickup ETA
+    pub pickup_speed_m_per_sec: u64,
     // Has this account been initialiazed
     pub is_initialized: bool,
 }
@@ -90,6 +118,32 @@ pub struct DriverInfra {
     pub dispute_cases: u64,
     // Number of dispute ride which infra lost
     pub cases_lost_in_dispute: u64,
+    // Number of jobs not yet closed, including disputed jobs
+    pub open_jobs: u64,
+    // Deposit waiting to be withdrawn
+    pub pending_withdrawal_cent: u64,
+    // When the pending withdrawal can be completed
+    pub withdrawal_available_time: Option<u64>,
+    // Reason for the current suspension
+    pub suspension_reason: Option<SuspensionReason>,
+    // When a timed suspension expires, none if indefinite
+    pub suspended_until: Option<u64>,
+    // Number of rides completed
+    pub completed_ride: u64,
+    // Completion rate discounted by dispute loss rate
+    pub reputation_basis_point: u16,
+    // Frozen until the country reviews the updated company info
+    pub company_review_pending: bool,
+    // Frozen until the deposit is topped up to the country minimum
+    pub is_underfunded: bool,
+}
+
+impl DriverInfra {
+    // Frozen while any freeze cause remains
+    pub fn refresh_frozen(&mut self) {
+        self.is_frozen =
+            self.suspension_reason.is_some() || self.company_review_pending || self.is_underfunded;
+    }
 }
 
 #[account]
@@ -130,12 +184,39 @@ pub struct CustomerInfra {
     pub dispute_cases: u64,
     // Number of dispute ride which infra lost
     pub cases_lost_in_dispute: u64,
+    // Number of jobs not yet closed, including disputed jobs
+    pub open_jobs: u64,
+    // Deposit waiting to be withdrawn
+    pub pending_withdrawal_cent: u64,
+    // When the pending withdrawal can be completed
+    pub withdrawal_available_time: Option<u64>,
+    // Reason for the current suspension
+    pub suspension_reason: Option<SuspensionReason>,
+    // When a timed suspension expires, none if indefinite
+    pub suspended_until: Option<u64>,
+    // Number of rides completed
+    pub completed_ride: u64,
+    // Completion rate discounted by dispute loss rate
+    pub reputation_basis_point: u16,
+    // Frozen until the country reviews the updated company info
+    pub company_review_pending: bool,
+    // Frozen until the deposit is topped up to the country minimum
+    pub is_underfunded: bool,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+impl CustomerInfra {
+    // Frozen while any freeze cause remains
+    pub fn refresh_frozen(&mut self) {
+        self.is_frozen =
+            self.suspension_reason.is_some() || self.company_review_pending || self.is_underfunded;
+    }
+}
+
+// Integer micro-degrees, 1.282720 is stored as 1_282_720
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub struct Coordinates {
-    pub lat: f64,
-    pub long: f64,
+    pub lat: i32,
+    pub long: i32,
 }
 
 #[account]
@@ -152,7 +233,7 @@ pub struct Driver {
     pub infra_authority: Pubkey,
     // Driver last location, to be updated every 10 seconds
     pub last_location: Coordinates,
-    // Driver location last updated time
+    // Driver location last updated time, the driver heartbeat
     pub location_last_update: u64,
     // Driver next destination
     pub next_location: Option<Coordinates>,
@@ -166,6 +247,40 @@ pub struct Driver {
     pub vehicle: Pubkey,
     // Number of seats in the vehicle
     pub number_of_seats: u8,
+    // Persistent profile of the driver
+    pub profile: Pubkey,
+    // Whether the driver can be matched
+    pub availability: DriverAvailability,
+    // Job currently holding the driver, one at a time
+    pub active_job: Option<Pubkey>,
+    // Customer infra holding a reservation on the driver
+    pub reserved_by: Option<Pubkey>,
+    // Reservation expiry
+    pub reserved_until: u64,
+}
+
+#[account]
+pub struct DriverProfile {
+    // Driver infra the driver works under
+    pub driver_infra: Pubkey,
+    // Driver UUID
+    pub driver_uuid: String,
+    // Driving license expiry
+    pub license_expiry: u64,
+    // Operator credential expiry, e.g. vocational license
+    pub credential_expiry: u64,
+    // Lifetime completed jobs
+    pub completed_job: u64,
+    // Lifetime jobs cancelled by the driver
+    pub cancelled_job: u64,
+    // Sum of customer ratings, 1 to 5 each
+    pub rating_sum: u64,
+    // Number of customer ratings
+    pub rating_count: u64,
+    // When the profile was created
+    pub created_time: u64,
+    // Highest driver infra quote nonce used, kept across sessions so quotes stay single use
+    pub quote_nonce: u64,
 }
 
 #[account]
@@ -194,6 +309,114 @@ pub struct Job {
     pub encrypted_data: String,
     // Encrypted RSA Pubkey with AES & IV
     pub encrypted_combined_rand_base64: String,
+    // When the driver picked up the customer
+    pub pickup_time: Option<u64>,
+    // When the status last changed
+    pub status_updated_time: u64,
+    // Service requested by the customer
+    pub service: u64,
+    // Special passenger types the customer requires
+    pub passenger_types: Vec<u64>,
+    // Number of passengers to seat
+    pub passenger_count: u8,
+    // Customer rating of the driver, 1 to 5
+    pub driver_rating: Option<u8>,
+    // Trip distance agreed at request, used for the final fare
+    pub trip_distance_m: u64,
+    // Trip duration estimated at request
+    pub trip_duration_sec: u64,
+    // Hash of the driver infra signed quote the fare was booked on
+    pub quote_hash: [u8; 32],
+    // Waiting fee charged at pickup
+    pub waiting_fee_cent: u64,
+    // When the driver is expected at pickup, set on acceptance
+    pub pickup_eta: Option<u64>,
+    // Country terms at request, fixed for the life of the job
+    pub terms: JobTerms,
+    // Cancellation tier applied when the job was cancelled
+    pub applied_cancellation_tier: Option<CancellationTier>,
+}
+
+#[account]
+pub struct Dispute {
+    // Job under dispute
+    pub job: Pubkey,
+    // Party that raised the dispute
+    pub opener: Actor,
+    // Reason given by the opener
+    pub reason: DisputeReason,
+    // Evidence hashes submitted by the driver infra
+    pub driver_evidence: Vec<[u8; 32]>,
+    // Evidence hashes submitted by the customer infra
+    pub customer_evidence: Vec<[u8; 32]>,
+    // When the dispute was raised
+    pub opened_time: u64,
+    // Last moment for the counterparty to respond
+    pub response_deadline: u64,
+    // When the counterparty first responded
+    pub responded_time: Option<u64>,
+    // Share of escrow awarded to the driver infra by the ruling
+    pub driver_award_basis_point: Option<u16>,
+    // When the country authority ruled
+    pub resolved_time: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DisputeReason {
+    NoShow,
+    Overcharge,
+    RouteDeviation,
+    Misconduct,
+    VehicleMismatch,
+    Other,
+}
+
+#[account]
+pub struct SlashRecord {
+    // Country the slash was made in
+    pub country_key: Pubkey,
+    // Slash record count (ID)
+    pub slash_count: u64,
+    // Slashed driver or customer infra
+    pub infra: Pubkey,
+    // Type of the slashed infra
+    pub infra_type: Actor,
+    // Reason given by the country authority
+    pub reason: SlashReason,
+    // Job the slash relates to
+    pub job: Option<Pubkey>,
+    // Dispute the slash relates to
+    pub dispute: Option<Pubkey>,
+    // Multiplier applied to the base slash amount
+    pub multiplier_basis_point: u64,
+    // Amount moved to the country treasury
+    pub amount: u64,
+    // When the slash happened
+    pub slashed_time: u64,
+    // Last moment the infra can appeal
+    pub appeal_deadline: u64,
+    // When the infra appealed
+    pub appealed_time: Option<u64>,
+    // When the global authority reversed the slash
+    pub reversed_time: Option<u64>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SuspensionReason {
+    Misconduct,
+    Fraud,
+    Compliance,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SlashReason {
+    DisputeLost,
+    FalseDispute,
+    NoShow,
+    FareManipulation,
+    ProtocolViolation,
+    Other,
 }
 
 #[account]
@@ -206,6 +429,8 @@ pub struct OfferedService {
     pub service_count: u64,
     // Is this an available option
     pub is_valid: bool,
+    // Rejected on review, listing fee refunded
+    pub is_rejected: bool,
     // Check is initialize for counter
     pub is_initialized: bool,
     // Deposit Payer
@@ -218,6 +443,8 @@ pub struct PassengerTypes {
     pub name: String,
     // Is this an available option
     pub is_valid: bool,
+    // Rejected on review, listing fee refunded
+    pub is_rejected: bool,
     // Check is initialize for counter
     pub is_initialized: bool,
     // Deposit Payer
@@ -232,24 +459,12 @@ pub struct Vehicle {
     pub number_of_seats: u8,
     // Is this an available option
     pub is_valid: bool,
+    // Rejected on review, listing fee refunded
+    pub is_rejected: bool,
     // Deposit Payer
     pub initializer: Pubkey,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
-pub enum Status {
-    Init,
-    JobAccepted,
-    RejectedByDriver,
-    Arrived, // No need
-    Started, // No need
-    Completed,
-    CancelledByDriver,
-    CancelledByCustomer,
-    DisputeByDriver,
-    DisputeByCustomer,
-}
-
 // #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 // pub enum JobType {
 //     Ride4Seater,
@@ -259,6 +474,90 @@ pub enum Status {
 //     PremiumSeater,
 // }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct DepositTier {
+    // Tier applies to infras with reputation below this
+    pub below_reputation_basis_point: u16,
+    // Multiplier on the minimum deposit
+    pub deposit_multiplier_basis_point: u32,
+}
+
+// Lifecycle stage a cancellation tier counts from
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CancellationStage {
+    // From acceptance
+    AfterAcceptance,
+    // From arrival at pickup
+    AfterArrival,
+    // From the pickup ETA, the driver is late
+    EtaBreach,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CancellationCharge {
+    // Fixed fee in local stable mint
+    FlatCent(u64),
+    // Share of the fare
+    FareBasisPoint(u16),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct CancellationTier {
+    pub stage: CancellationStage,
+    // Party cancelling the job
+    pub cancelled_by: Actor,
+    // Tier applies once this long has passed since the stage started
+    pub after_sec: u64,
+    pub charge: CancellationCharge,
+}
+
+// Cancellation outcome, the tier is also recorded on the job
+#[event]
+pub struct JobCancelled {
+    pub job: Pubkey,
+    pub status: Status,
+    pub tier: Option<CancellationTier>,
+    pub fee_cent: u64,
+}
+
+// Country fee and timing parameters a job is bound to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug)]
+pub struct JobTerms {
+    pub base_rate_cent: u64,
+    pub min_km_rate_cent: u64,
+    pub min_min_fee_cent: u64,
+    pub waiting_fee_sec: u64,
+    pub waiting_fee_cent: u64,
+    pub max_waiting_fee_cent: u64,
+    pub no_show_wait_sec: u64,
+    pub no_show_fee_cent: u64,
+    pub cancellation_tiers: Vec<CancellationTier>,
+    pub pickup_speed_m_per_sec: u64,
+    pub finalize_duration_sec: u64,
+    pub dispute_waitout_period: u64,
+    pub keeper_reward_cent: u64,
+}
+
+impl JobTerms {
+    pub fn snapshot(country: &Country) -> Self {
+        JobTerms {
+            base_rate_cent: country.base_rate_cent,
+            min_km_rate_cent: country.min_km_rate_cent,
+            min_min_fee_cent: country.min_min_fee_cent,
+            waiting_fee_sec: country.waiting_fee_sec,
+            waiting_fee_cent: country.waiting_fee_cent,
+            max_waiting_fee_cent: country.max_waiting_fee_cent,
+            no_show_wait_sec: country.no_show_wait_sec,
+            no_show_fee_cent: country.no_show_fee_cent,
+            cancellation_tiers: country.cancellation_tiers.clone(),
+            pickup_speed_m_per_sec: country.pickup_speed_m_per_sec,
+            finalize_duration_sec: country.finalize_duration_sec,
+            dispute_waitout_period: country.dispute_waitout_period,
+            keeper_reward_cent: country.keeper_reward_cent,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct Distribution {
     pub provider: Pubkey,
@@ -270,9 +569,17 @@ const PREFIX: usize = 4;
 const ALPHA3: usize = PREFIX + 3;
 const PUBKEY: usize = 32;
 const BOOL: usize = 1;
+const OPTION: usize = 1;
 const U8: usize = 1;
 const U16: usize = 2;
+const U32: usize = 4;
 const U64: usize = 8;
+const HASH: usize = 32;
+
+pub const MAX_DISPUTE_EVIDENCE: usize = 4;
+pub const MAX_DEPOSIT_TIERS: usize = 4;
+pub const MAX_CANCELLATION_TIERS: usize = 8;
+pub const MAX_DRIVER_RATING: u8 = 5;
 
 impl Global {
     pub fn len() -> usize {
@@ -299,13 +606,35 @@ impl Country {
             + U64
             + U64
             + U64
-            + U64
-            + U64
-            + U64
+            + U64 // keeper reward
+            + U64 // infra unbonding period
+            + U64 // deposit grace period
+            + U64 // min deposit raised time
+            + U64 // slash appeal period
+            + U64 // slash counter
+            + U16 // min reputation
+            + U64 // reputation min matched ride
+            + PREFIX + (U16 + U32) * MAX_DEPOSIT_TIERS // deposit tiers
+            + U64 // max reservation hold
+            + U64 // driver heartbeat timeout
+            + U64 // max waiting fee
+            + U64 // no-show wait
+            + U64 // no-show fee
+            + PREFIX + CancellationTier::len() * MAX_CANCELLATION_TIERS // cancellation tiers
+            + U64 // pickup speed
             + BOOL
     }
 }
 
+impl CancellationTier {
+    pub fn len() -> usize {
+        1 // Stage
+            + 1 // Cancelled by
+            + U64 // After sec
+            + (1 + U64) // Charge
+    }
+}
+
 impl DriverInfra {
     pub fn len() -> usize {
         DISCRIMINATOR
@@ -322,6 +651,15 @@ impl DriverInfra {
             + U64
             + U64
             + U64
+            + U64 // Open jobs
+            + U64 // Pending withdrawal
+            + (OPTION + U64) // Withdrawal available time
+            + (OPTION + 1) // Suspension reason
+            + (OPTION + U64) // Suspended until
+            + U64 // Completed ride
+            + U16 // Reputation
+            + BOOL // Company review pending
+            + BOOL // Underfunded
     }
 }
 
@@ -347,18 +685,39 @@ impl Driver {
             + (PREFIX + uuid.chars().count())
             + (PREFIX + rsa_pubkey.chars().count())
             + PUBKEY // Infra authority
-            + std::mem::size_of::<f64>() * 2 // Last location
+            + U32 * 2 // Last location
             + U64 // location last update
-            + std::mem::size_of::<f64>() * 2 // next location
+            + (OPTION + U32 * 2) // next location
             + PUBKEY // country key
             + (PREFIX + offered_service.len() * U64) // offered service
             + (PREFIX + passenger_types.len() * U64) // passenger type
             + PUBKEY // Vehicle
             + U8 // Number of seats
+            + PUBKEY // Profile
+            + 1 // Availability
+            + (OPTION + PUBKEY) // Active job
+            + (OPTION + PUBKEY) // Reserved by
+            + U64 // Reserved until
             + 200
     }
 }
 
+impl DriverProfile {
+    pub fn len(uuid: &String) -> usize {
+        DISCRIMINATOR
+            + PUBKEY // Driver infra
+            + (PREFIX + uuid.chars().count())
+            + U64 // License expiry
+            + U64 // Credential expiry
+            + U64 // Completed job
+            + U64 // Cancelled job
+            + U64 // Rating sum
+            + U64 // Rating count
+            + U64 // Created time
+            + U64 // Quote nonce
+    }
+}
+
 impl CustomerInfra {
     pub fn len() -> usize {
         DISCRIMINATOR
@@ -375,6 +734,15 @@ impl CustomerInfra {
             + U64
             + U64
             + U64
+            + U64 // Open jobs
+            + U64 // Pending withdrawal
+            + (OPTION + U64) // Withdrawal available time
+            + (OPTION + 1) // Suspension reason
+            + (OPTION + U64) // Suspended until
+            + U64 // Completed ride
+            + U16 // Reputation
+            + BOOL // Company review pending
+            + BOOL // Underfunded
     }
 }
 
@@ -384,28 +752,97 @@ impl Job {
         distribution_len: &u8,
         encrypted_data: &String,
         encrypted_combined_rand_base64: &String,
+        passenger_types: &Vec<u64>,
     ) -> usize {
         DISCRIMINATOR
             + PUBKEY // Driver infra
             + PUBKEY // Rider infra
-            + U8 // Job Count
-            + PUBKEY // Settlement protocol
+            + U64 // Job Count
+            + (OPTION + PUBKEY) // Settlement protocol
             + U64 // Total fees
             + (PREFIX + (PUBKEY + U16) * *distribution_len as usize)
             + 1 // Status
             + U64 // Job initialized time
-            + U64 // Job start time
-            + U64 // Driver arrived time
-            + U64 // Job end time
+            + (OPTION + U64) // Job start time
+            + (OPTION + U64) // Driver arrived time
+            + (OPTION + U64) // Job end time
             + (PREFIX + driver_uuid.chars().count()) // Driver UUID
             + (PREFIX + encrypted_data.chars().count())
             + (PREFIX + encrypted_combined_rand_base64.chars().count())
+            + (OPTION + U64) // Pickup time
+            + U64 // Status updated time
+            + U64 // Service
+            + (PREFIX + passenger_types.len() * U64) // Passenger types
+            + U8 // Passenger count
+            + (OPTION + U8) // Driver rating
+            + U64 // Trip distance
+            + U64 // Trip duration
+            + HASH // Quote hash
+            + U64 // Waiting fee
+            + (OPTION + U64) // Pickup ETA
+            + JobTerms::len() // Terms
+            + (OPTION + CancellationTier::len()) // Applied cancellation tier
+    }
+}
+
+impl JobTerms {
+    pub fn len() -> usize {
+        U64 * 3 // Rate card
+            + U64 * 3 // Waiting fee
+            + U64 * 2 // No-show
+            + PREFIX + CancellationTier::len() * MAX_CANCELLATION_TIERS // Cancellation tiers
+            + U64 // Pickup speed
+            + U64 // Finalize duration
+            + U64 // Dispute waitout
+            + U64 // Keeper reward
+    }
+}
+
+impl Dispute {
+    pub fn len() -> usize {
+        DISCRIMINATOR
+            + PUBKEY // Job
+            + 1 // Opener
+            + 1 // Reason
+            + (PREFIX + HASH * MAX_DISPUTE_EVIDENCE) // Driver evidence
+            + (PREFIX + HASH * MAX_DISPUTE_EVIDENCE) // Customer evidence
+            + U64 // Opened time
+            + U64 // Response deadline
+            + (OPTION + U64) // Responded time
+            + (OPTION + U16) // Driver award
+            + (OPTION + U64) // Resolved time
+    }
+}
+
+impl SlashRecord {
+    pub fn len() -> usize {
+        DISCRIMINATOR
+            + PUBKEY // Country
+            + U64 // Slash count
+            + PUBKEY // Infra
+            + 1 // Infra type
+            + 1 // Reason
+            + (OPTION + PUBKEY) // Job
+            + (OPTION + PUBKEY) // Dispute
+            + U64 // Multiplier
+            + U64 // Amount
+            + U64 // Slashed time
+            + U64 // Appeal deadline
+            + (OPTION + U64) // Appealed time
+            + (OPTION + U64) // Reversed time
     }
 }
 
 impl OfferedService {
     pub fn len(name: &String) -> usize {
-        DISCRIMINATOR + PUBKEY + (PREFIX + name.chars().count()) + U64 + BOOL + BOOL + PUBKEY
+        DISCRIMINATOR
+            + PUBKEY
+            + (PREFIX + name.chars().count())
+            + U64
+            + BOOL
+            + BOOL // Rejected
+            + BOOL
+            + PUBKEY
     }
 }
 
@@ -416,12 +853,13 @@ impl Vehicle {
             + (PREFIX + model.chars().count())
             + U8
             + BOOL
+            + BOOL // Rejected
             + PUBKEY
     }
 }
 
 impl PassengerTypes {
     pub fn len(name: &String) -> usize {
-        DISCRIMINATOR + (PREFIX + name.chars().count()) + BOOL + BOOL + PUBKEY
+        DISCRIMINATOR + (PREFIX + name.chars().count()) + BOOL + BOOL + BOOL + PUBKEY
     }
 }
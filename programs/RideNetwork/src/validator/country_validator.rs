@@ -4,6 +4,7 @@ use anchor_spl::{associated_token::AssociatedToken, token::*};
 
 use crate::error::ErrorCode;
 use crate::state::*;
+use crate::{cancellation, reputation};
 
 // INITIALIZE
 #[derive(Accounts)]
@@ -20,6 +21,15 @@ pub struct InitOrUpdateCountry<'info> {
         associated_token::authority = country_state
     )]
     pub country_stable_account: Account<'info, TokenAccount>,
+    #[account(seeds=[b"global"], bump)]
+    pub global_state: Account<'info, Global>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint, // SGD Stables
+        associated_token::authority = global_state
+    )]
+    pub global_stable_account: Account<'info, TokenAccount>,
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
@@ -128,12 +138,79 @@ pub struct CustomerInfraSuspension<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// REINSTATE DRIVER INFRASTRUCTURE
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct ReinstateDriverInfra<'info> {
+    #[account(
+        seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
+        constraint = country_state.update_authority == country_authority.key()
+    )]
+    pub country_state: Account<'info, Country>,
+    #[account(mut)]
+    pub country_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()],
+        bump,
+        constraint = driver_infra.is_frozen @ ErrorCode::InfraNotFrozen
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    pub system_program: Program<'info, System>,
+}
+// REINSTATE CUSTOMER INFRASTRUCTURE
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct ReinstateCustomerInfra<'info> {
+    #[account(
+        seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
+        constraint = country_state.update_authority == country_authority.key()
+    )]
+    pub country_state: Account<'info, Country>,
+    #[account(mut)]
+    pub country_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()],
+        bump,
+        constraint = customer_infra.is_frozen @ ErrorCode::InfraNotFrozen
+    )]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    pub system_program: Program<'info, System>,
+}
+// LIFT EXPIRED DRIVER INFRASTRUCTURE SUSPENSION, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct LiftExpiredDriverInfraSuspension<'info> {
+    #[account(
+        mut,
+        seeds = [b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()],
+        bump,
+        constraint = driver_infra.is_frozen @ ErrorCode::InfraNotFrozen
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    pub caller: Signer<'info>,
+}
+// LIFT EXPIRED CUSTOMER INFRASTRUCTURE SUSPENSION, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct LiftExpiredCustomerInfraSuspension<'info> {
+    #[account(
+        mut,
+        seeds = [b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()],
+        bump,
+        constraint = customer_infra.is_frozen @ ErrorCode::InfraNotFrozen
+    )]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    pub caller: Signer<'info>,
+}
+
 // SLASH DRIVER INFRASTRUCTURE
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_infra_count: u64)]
 pub struct DriverInfraSlash<'info> {
     #[account(
-        seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
+        mut, seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
         constraint = country_state.update_authority == country_authority.key()
     )]
     pub country_state: Account<'info, Country>,
@@ -148,8 +225,8 @@ pub struct DriverInfraSlash<'info> {
     pub driver_infra: Account<'info, DriverInfra>,
     #[account(
         mut,
-        constraint = driver_stable_account.mint == mint.key(), // SGD Stables
-        constraint = driver_stable_account.owner == country_state.key(),
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
     )]
     pub country_stable_account: Account<'info, TokenAccount>,
     #[account(
@@ -159,6 +236,14 @@ pub struct DriverInfraSlash<'info> {
         constraint = driver_stable_account.amount >= country_state.base_slash_amount,
     )]
     pub driver_stable_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = country_authority,
+        space = SlashRecord::len(),
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &country_state.slash_counter.to_le_bytes()], bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -168,7 +253,7 @@ pub struct DriverInfraSlash<'info> {
 #[instruction(alpha3_country_code: String, customer_infra_count: u64)]
 pub struct CustomerInfraSlash<'info> {
     #[account(
-        seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
+        mut, seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump,
         constraint = country_state.update_authority == country_authority.key()
     )]
     pub country_state: Account<'info, Country>,
@@ -183,8 +268,8 @@ pub struct CustomerInfraSlash<'info> {
     pub customer_infra: Account<'info, CustomerInfra>,
     #[account(
         mut,
-        constraint = customer_stable_account.mint == mint.key(), // SGD Stables
-        constraint = customer_stable_account.owner == country_state.key(),
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
     )]
     pub country_stable_account: Account<'info, TokenAccount>,
     #[account(
@@ -194,6 +279,14 @@ pub struct CustomerInfraSlash<'info> {
         constraint = customer_stable_account.amount >= country_state.base_slash_amount,
     )]
     pub customer_stable_account: Account<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = country_authority,
+        space = SlashRecord::len(),
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &country_state.slash_counter.to_le_bytes()], bump
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -204,9 +297,6 @@ pub struct InitOrUpdateCountryParam {
     pub platform_fee_basis_point: Option<u16>,
     pub waiting_fee_sec: Option<u64>,
     pub waiting_fee_cent: Option<u64>,
-    pub driver_cancellation_fee_sec: Option<u64>,
-    pub customer_cancellation_fee_sec: Option<u64>,
-    pub cancellation_fee_cent: Option<u64>,
     pub base_rate_cent: Option<u64>,
     pub min_km_rate_cent: Option<u64>,
     pub min_min_fee_cent: Option<u64>,
@@ -215,6 +305,20 @@ pub struct InitOrUpdateCountryParam {
     pub min_customer_infra_deposit: Option<u64>,
     pub dispute_waitout_period: Option<u64>,
     pub base_slash_amount: Option<u64>,
+    pub keeper_reward_cent: Option<u64>,
+    pub infra_unbonding_period_sec: Option<u64>,
+    pub deposit_grace_period_sec: Option<u64>,
+    pub slash_appeal_period_sec: Option<u64>,
+    pub min_reputation_basis_point: Option<u16>,
+    pub reputation_min_matched_ride: Option<u64>,
+    pub deposit_tiers: Option<Vec<DepositTier>>,
+    pub max_reservation_hold_sec: Option<u64>,
+    pub driver_heartbeat_timeout_sec: Option<u64>,
+    pub max_waiting_fee_cent: Option<u64>,
+    pub no_show_wait_sec: Option<u64>,
+    pub no_show_fee_cent: Option<u64>,
+    pub cancellation_tiers: Option<Vec<CancellationTier>>,
+    pub pickup_speed_m_per_sec: Option<u64>,
 }
 
 impl InitOrUpdateCountryParam {
@@ -222,9 +326,6 @@ impl InitOrUpdateCountryParam {
         if self.platform_fee_basis_point.is_none()
             || self.waiting_fee_sec.is_none()
             || self.waiting_fee_cent.is_none()
-            || self.driver_cancellation_fee_sec.is_none()
-            || self.customer_cancellation_fee_sec.is_none()
-            || self.cancellation_fee_cent.is_none()
             || self.base_rate_cent.is_none()
             || self.min_km_rate_cent.is_none()
             || self.min_min_fee_cent.is_none()
@@ -233,6 +334,20 @@ impl InitOrUpdateCountryParam {
             || self.min_customer_infra_deposit.is_none()
             || self.dispute_waitout_period.is_none()
             || self.base_slash_amount.is_none()
+            || self.keeper_reward_cent.is_none()
+            || self.infra_unbonding_period_sec.is_none()
+            || self.deposit_grace_period_sec.is_none()
+            || self.slash_appeal_period_sec.is_none()
+            || self.min_reputation_basis_point.is_none()
+            || self.reputation_min_matched_ride.is_none()
+            || self.deposit_tiers.is_none()
+            || self.max_reservation_hold_sec.is_none()
+            || self.driver_heartbeat_timeout_sec.is_none()
+            || self.max_waiting_fee_cent.is_none()
+            || self.no_show_wait_sec.is_none()
+            || self.no_show_fee_cent.is_none()
+            || self.cancellation_tiers.is_none()
+            || self.pickup_speed_m_per_sec.is_none()
         {
             return err!(ErrorCode::InvalidCreateCountryParams);
         };
@@ -242,9 +357,6 @@ impl InitOrUpdateCountryParam {
         country_state.customer_infra_counter = 0;
         country_state.waiting_fee_sec = self.waiting_fee_sec.unwrap();
         country_state.waiting_fee_cent = self.waiting_fee_cent.unwrap();
-        country_state.driver_cancellation_fee_sec = self.driver_cancellation_fee_sec.unwrap();
-        country_state.customer_cancellation_fee_sec = self.customer_cancellation_fee_sec.unwrap();
-        country_state.cancellation_fee_cent = self.cancellation_fee_cent.unwrap();
         country_state.base_rate_cent = self.base_rate_cent.unwrap();
         country_state.min_km_rate_cent = self.min_km_rate_cent.unwrap();
         country_state.min_min_fee_cent = self.min_min_fee_cent.unwrap();
@@ -253,6 +365,25 @@ impl InitOrUpdateCountryParam {
         country_state.min_customer_infra_deposit = self.min_customer_infra_deposit.unwrap();
         country_state.base_slash_amount = self.base_slash_amount.unwrap();
         country_state.dispute_waitout_period = self.dispute_waitout_period.unwrap();
+        country_state.keeper_reward_cent = self.keeper_reward_cent.unwrap();
+        country_state.infra_unbonding_period_sec = self.infra_unbonding_period_sec.unwrap();
+        country_state.deposit_grace_period_sec = self.deposit_grace_period_sec.unwrap();
+        country_state.slash_appeal_period_sec = self.slash_appeal_period_sec.unwrap();
+        country_state.slash_counter = 0;
+        country_state.min_reputation_basis_point = self.min_reputation_basis_point.unwrap();
+        country_state.reputation_min_matched_ride = self.reputation_min_matched_ride.unwrap();
+        let deposit_tiers = self.deposit_tiers.clone().unwrap();
+        reputation::validate_deposit_tiers(&deposit_tiers)?;
+        country_state.deposit_tiers = deposit_tiers;
+        country_state.max_reservation_hold_sec = self.max_reservation_hold_sec.unwrap();
+        country_state.driver_heartbeat_timeout_sec = self.driver_heartbeat_timeout_sec.unwrap();
+        country_state.max_waiting_fee_cent = self.max_waiting_fee_cent.unwrap();
+        country_state.no_show_wait_sec = self.no_show_wait_sec.unwrap();
+        country_state.no_show_fee_cent = self.no_show_fee_cent.unwrap();
+        let cancellation_tiers = self.cancellation_tiers.clone().unwrap();
+        cancellation::validate_cancellation_tiers(&cancellation_tiers)?;
+        country_state.cancellation_tiers = cancellation_tiers;
+        country_state.pickup_speed_m_per_sec = self.pickup_speed_m_per_sec.unwrap();
         Ok(())
     }
     pub fn update_or_same(&self, country_state: &mut Country) -> Result<()> {
@@ -265,15 +396,6 @@ impl InitOrUpdateCountryParam {
         country_state.waiting_fee_cent = self
             .waiting_fee_cent
             .unwrap_or(country_state.waiting_fee_cent);
-        country_state.driver_cancellation_fee_sec = self
-            .driver_cancellation_fee_sec
-            .unwrap_or(country_state.driver_cancellation_fee_sec);
-        country_state.customer_cancellation_fee_sec = self
-            .customer_cancellation_fee_sec
-            .unwrap_or(country_state.customer_cancellation_fee_sec);
-        country_state.cancellation_fee_cent = self
-            .cancellation_fee_cent
-            .unwrap_or(country_state.cancellation_fee_cent);
         country_state.base_rate_cent = self.base_rate_cent.unwrap_or(country_state.base_rate_cent);
         country_state.min_km_rate_cent = self
             .min_km_rate_cent
@@ -296,6 +418,50 @@ impl InitOrUpdateCountryParam {
         country_state.dispute_waitout_period = self
             .dispute_waitout_period
             .unwrap_or(country_state.dispute_waitout_period);
+        country_state.keeper_reward_cent = self
+            .keeper_reward_cent
+            .unwrap_or(country_state.keeper_reward_cent);
+        country_state.infra_unbonding_period_sec = self
+            .infra_unbonding_period_sec
+            .unwrap_or(country_state.infra_unbonding_period_sec);
+        country_state.deposit_grace_period_sec = self
+            .deposit_grace_period_sec
+            .unwrap_or(country_state.deposit_grace_period_sec);
+        country_state.slash_appeal_period_sec = self
+            .slash_appeal_period_sec
+            .unwrap_or(country_state.slash_appeal_period_sec);
+        country_state.min_reputation_basis_point = self
+            .min_reputation_basis_point
+            .unwrap_or(country_state.min_reputation_basis_point);
+        country_state.reputation_min_matched_ride = self
+            .reputation_min_matched_ride
+            .unwrap_or(country_state.reputation_min_matched_ride);
+        if let Some(deposit_tiers) = &self.deposit_tiers {
+            reputation::validate_deposit_tiers(deposit_tiers)?;
+            country_state.deposit_tiers = deposit_tiers.clone();
+        }
+        country_state.max_reservation_hold_sec = self
+            .max_reservation_hold_sec
+            .unwrap_or(country_state.max_reservation_hold_sec);
+        country_state.driver_heartbeat_timeout_sec = self
+            .driver_heartbeat_timeout_sec
+            .unwrap_or(country_state.driver_heartbeat_timeout_sec);
+        country_state.max_waiting_fee_cent = self
+            .max_waiting_fee_cent
+            .unwrap_or(country_state.max_waiting_fee_cent);
+        country_state.no_show_wait_sec = self
+            .no_show_wait_sec
+            .unwrap_or(country_state.no_show_wait_sec);
+        country_state.no_show_fee_cent = self
+            .no_show_fee_cent
+            .unwrap_or(country_state.no_show_fee_cent);
+        if let Some(cancellation_tiers) = &self.cancellation_tiers {
+            cancellation::validate_cancellation_tiers(cancellation_tiers)?;
+            country_state.cancellation_tiers = cancellation_tiers.clone();
+        }
+        country_state.pickup_speed_m_per_sec = self
+            .pickup_speed_m_per_sec
+            .unwrap_or(country_state.pickup_speed_m_per_sec);
 
         Ok(())
     }
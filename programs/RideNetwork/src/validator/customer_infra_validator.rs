@@ -79,6 +79,99 @@ pub struct UpdateCustomerInfraBasisPoint<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// TOP UP DEPOSIT
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct TopUpCustomerInfraDeposit<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    pub customer_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = customer_infra_owner_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_owner_stable.owner == customer_infra_owner.key(),
+    )]
+    pub customer_infra_owner_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
+
+// REQUEST DEPOSIT WITHDRAWAL
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct RequestCustomerInfraWithdrawal<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    pub customer_infra_owner: Signer<'info>,
+    #[account(
+        constraint = customer_infra_stable.mint == country_state.stable_mint, // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+}
+
+// COMPLETE DEPOSIT WITHDRAWAL AFTER UNBONDING
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct CompleteCustomerInfraWithdrawal<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    pub customer_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = customer_infra_owner_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_owner_stable.owner == customer_infra_owner.key(),
+    )]
+    pub customer_infra_owner_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
+
+// FREEZE UNDERFUNDED INFRA, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64)]
+pub struct FreezeUnderfundedCustomerInfra<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(
+        constraint = customer_infra_stable.mint == country_state.stable_mint, // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, ZeroCopyAccessor)]
 pub struct InitCustomerInfraParam {
     pub customer_infra_count: u64,
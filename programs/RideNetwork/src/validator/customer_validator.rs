@@ -3,22 +3,45 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Transfer};
 use anchor_spl::{associated_token::AssociatedToken, token::*};
 
+use crate::error::ErrorCode;
+use crate::quote::DriverQuote;
 use crate::state::*;
 
 // CUSTOMER REQUEST RIDE
 #[derive(Accounts)]
-#[instruction(alpha3_country_code: String, driver_uuid: String, job_count: u64, customer_infra_count: u64, driver_infra_count: u64, distribution_len: u8, encrypted_data: String, encrypted_combined_rand_base64: String, total_fees: u64)]
+#[instruction(alpha3_country_code: String, driver_uuid: String, job_count: u64, customer_infra_count: u64, driver_infra_count: u64, distribution_len: u8, encrypted_data: String, encrypted_combined_rand_base64: String, driver_quote: DriverQuote, trip: RideTripParam)]
 pub struct CustomerRequestRide<'info> {
     #[account(seeds=[b"global".as_ref()], bump)]
     pub global_state: Box<Account<'info, Global>>,
-    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !customer_infra.is_frozen @ ErrorCode::InfraFrozen,
+    )]
     pub customer_infra: Box<Account<'info, CustomerInfra>>,
     #[account(
         mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.job_counter == job_count, // Job PDA and dispute PDA must be unique
+        constraint = driver_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !driver_infra.is_frozen @ ErrorCode::InfraFrozen,
+        constraint = driver_quote.driver_infra == driver_infra.key() @ ErrorCode::InvalidQuoteSignature,
     )]
     pub driver_infra: Box<Account<'info, DriverInfra>>,
-    #[account(init, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump, payer = customer_infra_owner, space = Job::len(&driver_uuid, &distribution_len, &encrypted_data, &encrypted_combined_rand_base64))]
+    #[account(init, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump, payer = customer_infra_owner, space = Job::len(&driver_uuid, &distribution_len, &encrypted_data, &encrypted_combined_rand_base64, &trip.passenger_types))]
     pub job: Box<Account<'info, Job>>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key(),
+        constraint = driver.availability == DriverAvailability::Online @ ErrorCode::DriverNotOnline,
+        constraint = driver.active_job.is_none() @ ErrorCode::DriverAlreadyBooked,
+        constraint = driver_quote.driver_uuid == driver_uuid @ ErrorCode::InvalidQuoteSignature
+    )]
+    pub driver: Box<Account<'info, Driver>>,
+    // Holds the quote nonce across driver sessions
+    #[account(mut, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump)]
+    pub driver_profile: Box<Account<'info, DriverProfile>>,
     #[account(
         mut,
         constraint = customer_infra.update_authority == customer_infra_owner.key()
@@ -28,7 +51,7 @@ pub struct CustomerRequestRide<'info> {
         mut,
         constraint = customer_infra_owner_stable.mint == mint.key(), // SGD Stables
         constraint = customer_infra_owner_stable.owner == customer_infra_owner.key(),
-        constraint = customer_infra_owner_stable.amount >= total_fees,
+        constraint = customer_infra_owner_stable.amount >= driver_quote.fare_cent,
     )]
     pub customer_infra_owner_stable: Box<Account<'info, TokenAccount>>,
     #[account(
@@ -46,21 +69,89 @@ pub struct CustomerRequestRide<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+    /// CHECK: Instructions sysvar, holds the driver infra quote signature
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+}
+
+// Committed trip the quote and the rate card are checked against
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, ZeroCopyAccessor)]
+pub struct RideTripParam {
+    pub passenger_types: Vec<u64>,
+    pub passenger_count: u8,
+    pub trip_distance_m: u64,
+    pub trip_duration_sec: u64,
+}
+
+// CUSTOMER RESERVE DRIVER
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, driver_infra_count: u64, driver_uuid: String)]
+pub struct CustomerReserveDriver<'info> {
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !customer_infra.is_frozen @ ErrorCode::InfraFrozen,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key(),
+        constraint = driver.availability == DriverAvailability::Online @ ErrorCode::DriverNotOnline,
+        constraint = driver.active_job.is_none() @ ErrorCode::DriverAlreadyBooked
+    )]
+    pub driver: Box<Account<'info, Driver>>,
+    pub customer_infra_owner: Signer<'info>,
+}
+
+// CUSTOMER RELEASE DRIVER
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, driver_uuid: String)]
+pub struct CustomerReleaseDriver<'info> {
+    #[account(
+        seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.reserved_by == Some(customer_infra.key()) @ ErrorCode::NotReservationHolder
+    )]
+    pub driver: Account<'info, Driver>,
+    pub customer_infra_owner: Signer<'info>,
 }
 
 // DRIVER ACCEPTED JOB
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, customer_infra_count: u64, driver_infra_count: u64, driver_uuid: String, job_count: u64)]
 pub struct DriverAcceptedJob<'info> {
-    #[account(mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump)]
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.availability == DriverAvailability::Online @ ErrorCode::DriverNotOnline
+    )]
     pub driver: Account<'info, Driver>,
-    #[account(mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !driver_infra.is_frozen @ ErrorCode::InfraFrozen,
+    )]
     pub driver_infra: Account<'info, DriverInfra>,
-    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !customer_infra.is_frozen @ ErrorCode::InfraFrozen,
+    )]
     pub customer_infra: Account<'info, CustomerInfra>,
     #[account(
         mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
-        constraint = job.driver_infra == driver_infra.key()
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+        constraint = job.driver_uuid == driver.driver_uuid,
+        constraint = driver.active_job == Some(job.key()) @ ErrorCode::DriverAlreadyBooked
     )]
     pub job: Account<'info, Job>,
     #[account(
@@ -72,6 +163,7 @@ pub struct DriverAcceptedJob<'info> {
 }
 
 // Customer CANCEL RIDE
+// Not gated on verification or freeze so in-flight jobs can always wind down
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_uuid: String, customer_infra_count: u64, driver_infra_count: u64, job_counter: u64)]
 pub struct CustomerCancelRide<'info> {
@@ -88,13 +180,24 @@ pub struct CustomerCancelRide<'info> {
         constraint = driver.infra_authority == driver_infra.key()
     )]
     pub driver: Box<Account<'info, Driver>>,
-    #[account(mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_counter.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_counter.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+        constraint = job.driver_uuid == driver.driver_uuid,
+    )]
     pub job: Box<Account<'info, Job>>,
     #[account(
         mut,
         constraint = customer_infra.update_authority == customer_infra_owner.key()
     )]
     pub customer_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = job_esrow_stable.mint == mint.key(), // SGD Stables
+        constraint = job_esrow_stable.owner == job.key(),
+    )]
+    pub job_esrow_stable: Account<'info, TokenAccount>,
     #[account(
         mut,
         constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
@@ -107,6 +210,7 @@ pub struct CustomerCancelRide<'info> {
         constraint = driver_infra_stable.owner == driver_infra.key(),
     )]
     pub driver_infra_stable: Account<'info, TokenAccount>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -114,16 +218,18 @@ pub struct CustomerCancelRide<'info> {
 
 // Customer RAISE ISSUE
 #[derive(Accounts)]
-#[instruction(customer_infra_count: u64, driver_infra_count: u64, job_counter: u64)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, driver_infra_count: u64, job_counter: u64)]
 pub struct CustomerRaiseIssue<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
     #[account(
-        seeds=[b"customer_infra".as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
         constraint = customer_infra.update_authority == customer_infra_owner.key()
     )]
     pub customer_infra: Account<'info, CustomerInfra>,
     #[account(mut)]
     pub customer_infra_owner: Signer<'info>,
-    #[account(seeds=[b"driver_infra".as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
     pub driver_infra: Account<'info, DriverInfra>,
     #[account(
         mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_counter.to_le_bytes()], bump,
@@ -131,5 +237,39 @@ pub struct CustomerRaiseIssue<'info> {
         constraint = job.customer_infra == customer_infra.key(),
     )]
     pub job: Account<'info, Job>,
+    // Only needed to release the driver when the job was still in progress
+    #[account(
+        mut, seeds=[b"driver".as_ref(), job.driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Option<Account<'info, Driver>>,
+    #[account(
+        init,
+        payer = customer_infra_owner,
+        space = Dispute::len(),
+        seeds = [b"dispute".as_ref(), job.key().as_ref()], bump
+    )]
+    pub dispute: Account<'info, Dispute>,
     pub system_program: Program<'info, System>,
 }
+
+// CUSTOMER RATE DRIVER
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, driver_infra_count: u64, job_count: u64)]
+pub struct CustomerRateDriver<'info> {
+    #[account(
+        seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.customer_infra == customer_infra.key()
+    )]
+    pub job: Box<Account<'info, Job>>,
+    #[account(mut, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), job.driver_uuid.as_ref()], bump)]
+    pub driver_profile: Box<Account<'info, DriverProfile>>,
+    pub customer_infra_owner: Signer<'info>,
+}
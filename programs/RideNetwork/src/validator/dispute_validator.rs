@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::*;
+
+use crate::state::*;
+
+// SUBMIT DISPUTE EVIDENCE, EITHER PARTY
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
+pub struct SubmitDisputeEvidence<'info> {
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    #[account(
+        seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+    )]
+    pub job: Account<'info, Job>,
+    #[account(
+        mut, seeds=[b"dispute".as_ref(), job.key().as_ref()], bump,
+        constraint = dispute.job == job.key(),
+    )]
+    pub dispute: Account<'info, Dispute>,
+    pub authority: Signer<'info>,
+}
+
+// RESOLVE DISPUTE, COUNTRY AUTHORITY
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
+pub struct ResolveDispute<'info> {
+    #[account(
+        seeds=[b"country", alpha3_country_code.as_ref()], bump,
+        constraint = country_state.update_authority == country_authority.key()
+    )]
+    pub country_state: Box<Account<'info, Country>>,
+    pub country_authority: Signer<'info>,
+    #[account(mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+        close = customer_infra
+    )]
+    pub job: Box<Account<'info, Job>>,
+    #[account(
+        mut, seeds=[b"dispute".as_ref(), job.key().as_ref()], bump,
+        constraint = dispute.job == job.key(),
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    #[account(
+        mut,
+        constraint = job_esrow_stable.mint == mint.key(), // SGD Stables
+        constraint = job_esrow_stable.owner == job.key(),
+    )]
+    pub job_esrow_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
+    )]
+    pub country_stable_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[b"global"], bump)]
+    pub global_state: Box<Account<'info, Global>>,
+    #[account(
+        mut,
+        constraint = global_stable_account.mint == mint.key(), // SGD Stables
+        constraint = global_stable_account.owner == global_state.key(),
+    )]
+    pub global_stable_account: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// CLAIM DEFAULT JUDGMENT, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
+pub struct ClaimDefaultJudgment<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+        close = customer_infra
+    )]
+    pub job: Box<Account<'info, Job>>,
+    #[account(
+        mut, seeds=[b"dispute".as_ref(), job.key().as_ref()], bump,
+        constraint = dispute.job == job.key(),
+    )]
+    pub dispute: Box<Account<'info, Dispute>>,
+    #[account(
+        mut,
+        constraint = job_esrow_stable.mint == mint.key(), // SGD Stables
+        constraint = job_esrow_stable.owner == job.key(),
+    )]
+    pub job_esrow_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
+    )]
+    pub country_stable_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[b"global"], bump)]
+    pub global_state: Box<Account<'info, Global>>,
+    #[account(
+        mut,
+        constraint = global_stable_account.mint == mint.key(), // SGD Stables
+        constraint = global_stable_account.owner == global_state.key(),
+    )]
+    pub global_stable_account: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub caller: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
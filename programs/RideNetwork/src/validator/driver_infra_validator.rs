@@ -81,6 +81,99 @@ pub struct UpdateDriverInfraBasisPoint<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// TOP UP DEPOSIT
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct TopUpDriverInfraDeposit<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    pub driver_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = driver_infra_owner_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_owner_stable.owner == driver_infra_owner.key(),
+    )]
+    pub driver_infra_owner_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
+
+// REQUEST DEPOSIT WITHDRAWAL
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct RequestDriverInfraWithdrawal<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    pub driver_infra_owner: Signer<'info>,
+    #[account(
+        constraint = driver_infra_stable.mint == country_state.stable_mint, // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+}
+
+// COMPLETE DEPOSIT WITHDRAWAL AFTER UNBONDING
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct CompleteDriverInfraWithdrawal<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    pub driver_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = driver_infra_owner_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_owner_stable.owner == driver_infra_owner.key(),
+    )]
+    pub driver_infra_owner_stable: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
+
+// FREEZE UNDERFUNDED INFRA, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64)]
+pub struct FreezeUnderfundedDriverInfra<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(
+        constraint = driver_infra_stable.mint == country_state.stable_mint, // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Box<Account<'info, TokenAccount>>,
+    pub caller: Signer<'info>,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, ZeroCopyAccessor)]
 pub struct InitDriverInfraParam {
     pub driver_infra_count: u64,
@@ -1,9 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::*;
 
+use crate::error::ErrorCode;
 use crate::state::*;
 
 // DRIVER START WORK
+// Remaining accounts: OfferedService per service ID, then PassengerTypes per passenger type ID
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String, rsa_pem_pubkey: String, services: Vec<u64>, passengers: Vec<u64>, vehicle_count: u64)]
 pub struct DriverStartWork<'info> {
@@ -11,12 +13,19 @@ pub struct DriverStartWork<'info> {
     pub country_state: Account<'info, Country>,
     #[account(
         mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
-        constraint = driver_infra.alpha3_country_code == alpha3_country_code
+        constraint = driver_infra.alpha3_country_code == alpha3_country_code,
+        constraint = driver_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !driver_infra.is_frozen @ ErrorCode::InfraFrozen,
     )]
     pub driver_infra: Account<'info, DriverInfra>,
+    #[account(seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump)]
+    pub driver_profile: Account<'info, DriverProfile>,
     #[account(init, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump, payer = driver_infra_authority, space = Driver::len(&driver_uuid, &rsa_pem_pubkey, &services, &passengers))]
     pub driver: Account<'info, Driver>,
-    #[account(seeds=[b"vehicle".as_ref(), &vehicle_count.to_le_bytes()], bump)]
+    #[account(
+        seeds=[b"vehicle".as_ref(), &vehicle_count.to_le_bytes()], bump,
+        constraint = vehicle.is_valid @ ErrorCode::InvalidCatalogItem
+    )]
     pub vehicle: Account<'info, Vehicle>,
     #[account(
         mut,
@@ -39,13 +48,66 @@ pub struct DriverUpdateLocation<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// DRIVER PROFILE INIT
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String)]
+pub struct InitDriverProfile<'info> {
+    #[account(
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(init, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump, payer = driver_infra_owner, space = DriverProfile::len(&driver_uuid))]
+    pub driver_profile: Account<'info, DriverProfile>,
+    #[account(mut)]
+    pub driver_infra_owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// DRIVER PROFILE UPDATE CREDENTIALS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String)]
+pub struct UpdateDriverProfileCredential<'info> {
+    #[account(
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(mut, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump)]
+    pub driver_profile: Account<'info, DriverProfile>,
+    pub driver_infra_owner: Signer<'info>,
+}
+
+// DRIVER SET AVAILABILITY
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String)]
+pub struct DriverSetAvailability<'info> {
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Account<'info, Driver>,
+    // Driver infra owner or the driver's location update authority
+    #[account(
+        constraint = driver_infra.update_authority == authority.key()
+            || driver.location_update_authority == authority.key()
+    )]
+    pub authority: Signer<'info>,
+}
+
 // DRIVER END JOB
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_uuid: String, driver_infra_count: u64)]
 pub struct DriverEndWork<'info> {
     #[account(mut, seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
     pub driver_infra: Account<'info, DriverInfra>,
-    #[account(mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump, close = driver_infra_owner)]
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump, close = driver_infra_owner,
+        constraint = driver.infra_authority == driver_infra.key(),
+        constraint = driver.active_job.is_none() @ ErrorCode::DriverHasActiveJob
+    )]
     pub driver: Account<'info, Driver>,
     #[account(
         mut,
@@ -55,7 +117,27 @@ pub struct DriverEndWork<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// MARK STALE DRIVER, PERMISSIONLESS
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String)]
+pub struct MarkStaleDriver<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump)]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Account<'info, Driver>,
+    /// CHECK: Session rent payer, receives the rent when the driver is closed
+    #[account(mut, constraint = driver_infra_owner.key() == driver_infra.update_authority)]
+    pub driver_infra_owner: AccountInfo<'info>,
+    pub caller: Signer<'info>,
+}
+
 // DRIVER COMPLETE JOB
+// Not gated on verification or freeze so in-flight jobs can always wind down
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_uuid: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
 pub struct DriverCompleteJob<'info> {
@@ -68,12 +150,18 @@ pub struct DriverCompleteJob<'info> {
     pub driver_infra: Box<Account<'info, DriverInfra>>,
     #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
     pub customer_infra: Box<Account<'info, CustomerInfra>>,
-    #[account(mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump)]
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
     pub driver: Box<Account<'info, Driver>>,
+    #[account(mut, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump)]
+    pub driver_profile: Box<Account<'info, DriverProfile>>,
     #[account(
         mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
         constraint = job.driver_infra == driver_infra.key(),
-        constraint = job.customer_infra == customer_infra.key()
+        constraint = job.customer_infra == customer_infra.key(),
+        constraint = job.driver_uuid == driver_uuid
     )]
     pub job: Box<Account<'info, Job>>,
     #[account(
@@ -99,12 +187,57 @@ pub struct DriverCompleteJob<'info> {
         constraint = customer_infra_stable.owner == customer_infra.key(),
     )]
     pub customer_infra_stable: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
+    )]
+    pub country_stable_account: Box<Account<'info, TokenAccount>>,
+    #[account(seeds=[b"global"], bump)]
+    pub global_state: Box<Account<'info, Global>>,
+    #[account(
+        mut,
+        constraint = global_stable_account.mint == mint.key(), // SGD Stables
+        constraint = global_stable_account.owner == global_state.key(),
+    )]
+    pub global_stable_account: Box<Account<'info, TokenAccount>>,
     #[account(constraint = mint.key() == country_state.stable_mint)]
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
+// DRIVER ARRIVED AT PICKUP
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, driver_uuid: String, job_count: u64)]
+pub struct DriverArrived<'info> {
+    #[account(
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.alpha3_country_code == alpha3_country_code,
+        constraint = driver_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !driver_infra.is_frozen @ ErrorCode::InfraFrozen,
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(
+        seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Account<'info, Driver>,
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.driver_uuid == driver.driver_uuid
+    )]
+    pub job: Account<'info, Job>,
+    // Driver infra owner or the driver's location update authority
+    #[account(
+        constraint = driver_infra.update_authority == authority.key()
+            || driver.location_update_authority == authority.key()
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
 // DRIVER PICKUP CUSTOMER
 #[derive(Accounts)]
 #[instruction(alpha3_country_code: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
@@ -112,15 +245,22 @@ pub struct DriverPickupCustomer<'info> {
     #[account(mut, seeds=[b"country", alpha3_country_code.as_ref()], bump)]
     pub country_state: Box<Account<'info, Country>>,
     #[account(
-        seeds=[b"driver_infra".as_ref(), driver_infra.key().as_ref()], bump,
-        constraint = driver_infra.alpha3_country_code == alpha3_country_code
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.alpha3_country_code == alpha3_country_code,
+        constraint = driver_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !driver_infra.is_frozen @ ErrorCode::InfraFrozen,
     )]
     pub driver_infra: Box<Account<'info, DriverInfra>>,
-    #[account(mut, seeds=[b"customer_infra".as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump,
+        constraint = customer_infra.is_verified @ ErrorCode::InfraNotVerified,
+        constraint = !customer_infra.is_frozen @ ErrorCode::InfraFrozen,
+    )]
     pub customer_infra: Box<Account<'info, CustomerInfra>>,
     #[account(
         mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
-        constraint = job.driver_infra == driver_infra.key()
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
     )]
     pub job: Box<Account<'info, Job>>,
     #[account(
@@ -128,11 +268,13 @@ pub struct DriverPickupCustomer<'info> {
     )]
     pub driver_infra_owner: Signer<'info>,
     #[account(
+        mut,
         constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
         constraint = driver_infra_stable.owner == driver_infra.key(),
     )]
     pub driver_infra_stable: Account<'info, TokenAccount>,
     #[account(
+        mut,
         constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
         constraint = customer_infra_stable.owner == customer_infra.key(),
     )]
@@ -159,12 +301,18 @@ pub struct DriverCancelJob<'info> {
     pub driver_infra: Box<Account<'info, DriverInfra>>,
     #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
     pub customer_infra: Box<Account<'info, CustomerInfra>>,
-    #[account(mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump)]
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
     pub driver: Box<Account<'info, Driver>>,
+    #[account(mut, seeds=[b"driver_profile".as_ref(), driver_infra.key().as_ref(), driver_uuid.as_ref()], bump)]
+    pub driver_profile: Box<Account<'info, DriverProfile>>,
     #[account(
         mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
         constraint = job.driver_infra == driver_infra.key(),
-        constraint = job.customer_infra == customer_infra.key()
+        constraint = job.customer_infra == customer_infra.key(),
+        constraint = job.driver_uuid == driver_uuid
     )]
     pub job: Box<Account<'info, Job>>,
     #[account(
@@ -196,12 +344,68 @@ pub struct DriverCancelJob<'info> {
     pub system_program: Program<'info, System>,
 }
 
+// DRIVER REPORT NO SHOW
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_uuid: String, driver_infra_count: u64, customer_infra_count: u64, job_count: u64)]
+pub struct DriverReportNoShow<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Box<Account<'info, Country>>,
+    #[account(
+        mut,
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
+        constraint = driver_infra.alpha3_country_code == alpha3_country_code
+    )]
+    pub driver_infra: Box<Account<'info, DriverInfra>>,
+    #[account(mut, seeds=[b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()], bump)]
+    pub customer_infra: Box<Account<'info, CustomerInfra>>,
+    #[account(
+        mut, seeds=[b"driver".as_ref(), driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Box<Account<'info, Driver>>,
+    #[account(
+        mut, seeds=[b"job".as_ref(), driver_infra.key().as_ref(), &job_count.to_le_bytes()], bump,
+        constraint = job.driver_infra == driver_infra.key(),
+        constraint = job.customer_infra == customer_infra.key(),
+        constraint = job.driver_uuid == driver_uuid
+    )]
+    pub job: Box<Account<'info, Job>>,
+    #[account(
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        constraint = job_esrow_stable.mint == mint.key(), // SGD Stables
+        constraint = job_esrow_stable.owner == job.key(),
+    )]
+    pub job_esrow_stable: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = driver_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = driver_infra_stable.owner == driver_infra.key(),
+    )]
+    pub driver_infra_stable: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = customer_infra_stable.mint == mint.key(), // SGD Stables
+        constraint = customer_infra_stable.owner == customer_infra.key(),
+    )]
+    pub customer_infra_stable: Account<'info, TokenAccount>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
 // DRIVER RAISE ISSUE
 #[derive(Accounts)]
-#[instruction(driver_infra_count: u64, job_count: u64)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, job_count: u64)]
 pub struct DriverRaiseIssue<'info> {
+    #[account(seeds=[b"country", alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
     #[account(
-        seeds=[b"driver_infra".as_ref(), driver_infra.key().as_ref()], bump,
+        seeds=[b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()], bump,
         constraint = driver_infra.update_authority == driver_infra_owner.key()
     )]
     pub driver_infra: Account<'info, DriverInfra>,
@@ -212,5 +416,18 @@ pub struct DriverRaiseIssue<'info> {
         constraint = job.driver_infra == driver_infra.key(),
     )]
     pub job: Account<'info, Job>,
+    // Only needed to release the driver when the job was still in progress
+    #[account(
+        mut, seeds=[b"driver".as_ref(), job.driver_uuid.as_ref()], bump,
+        constraint = driver.infra_authority == driver_infra.key()
+    )]
+    pub driver: Option<Account<'info, Driver>>,
+    #[account(
+        init,
+        payer = driver_infra_owner,
+        space = Dispute::len(),
+        seeds = [b"dispute".as_ref(), job.key().as_ref()], bump
+    )]
+    pub dispute: Account<'info, Dispute>,
     pub system_program: Program<'info, System>,
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token::*};
 
+use crate::error::ErrorCode;
 use crate::state::*;
 
 // INITIALIZE
@@ -33,7 +34,7 @@ pub struct InitOrUpdatePassengerTypes<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-// APPROVAL
+// APPROVAL OR REJECTION
 #[derive(Accounts)]
 #[instruction(passenger_type_count: u64)]
 pub struct PassengerTypeVerification<'info> {
@@ -42,28 +43,34 @@ pub struct PassengerTypeVerification<'info> {
         constraint = global_state.update_authority == global_authority.key()
     )]
     pub global_state: Account<'info, Global>,
-    #[account(seeds=[b"passenger_type".as_ref(), &passenger_type_count.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"passenger_type".as_ref(), &passenger_type_count.to_le_bytes()], bump,
+        constraint = !passenger_type.is_valid @ ErrorCode::CatalogItemAlreadyValid
+    )]
     pub passenger_type: Account<'info, PassengerTypes>,
     #[account(
+        mut,
         constraint = passenger_type_escrow_account.mint == mint.key(), // USDC Stables
         constraint = passenger_type_escrow_account.owner == passenger_type.key()
     )]
     pub passenger_type_escrow_account: Account<'info, TokenAccount>,
     #[account(
+        mut,
         constraint = global_token_account.mint == mint.key(), // USDC Stables
         constraint = global_token_account.owner == global_state.key()
     )]
     pub global_token_account: Account<'info, TokenAccount>,
     #[account(
+        mut,
         constraint = initializer_token_account.mint == mint.key(), // USDC Stables
         constraint = initializer_token_account.owner == passenger_type.initializer
     )]
     pub initializer_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Deposit payer, receives the escrow rent
+    #[account(mut, constraint = initializer.key() == passenger_type.initializer)]
+    pub initializer: AccountInfo<'info>,
     #[account(mut)]
     pub global_authority: Signer<'info>,
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
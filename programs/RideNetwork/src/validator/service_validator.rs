@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token::*};
 
+use crate::error::ErrorCode;
 use crate::state::*;
 
 // INITIALIZE
@@ -35,39 +36,46 @@ pub struct InitOrUpdateService<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-// APPROVAL
+// APPROVAL OR REJECTION
 #[derive(Accounts)]
-#[instruction(alpha3_country_code: String, service_count: u64)]
+#[instruction(alpha3_country_code: String, service_type_count: u64)]
 pub struct ServiceVerification<'info> {
     #[account(
         seeds=[b"global"], bump,
         constraint = global_state.update_authority == global_authority.key()
     )]
-    pub global_state: Account<'info, Global>,
+    pub global_state: Box<Account<'info, Global>>,
     #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
-    pub country_state: Account<'info, Country>,
-    #[account(seeds=[b"offered_service".as_ref(), alpha3_country_code.as_ref(), &service_count.to_le_bytes()], bump)]
-    pub service: Account<'info, OfferedService>,
+    pub country_state: Box<Account<'info, Country>>,
     #[account(
-        constraint = service_escrow_account.mint == mint.key(), // USDC Stables
-        constraint = service_escrow_account.owner == service.key()
+        mut, seeds=[b"offered_service".as_ref(), &service_type_count.to_le_bytes()], bump,
+        constraint = service_type.country_key == country_state.key(),
+        constraint = !service_type.is_valid @ ErrorCode::CatalogItemAlreadyValid
     )]
-    pub service_escrow_account: Account<'info, TokenAccount>,
+    pub service_type: Box<Account<'info, OfferedService>>,
+    #[account(
+        mut,
+        constraint = service_type_escrow_account.mint == mint.key(), // USDC Stables
+        constraint = service_type_escrow_account.owner == service_type.key()
+    )]
+    pub service_type_escrow_account: Box<Account<'info, TokenAccount>>,
     #[account(
+        mut,
         constraint = country_token_account.mint == mint.key(), // USDC Stables
         constraint = country_token_account.owner == country_state.key()
     )]
-    pub country_token_account: Account<'info, TokenAccount>,
+    pub country_token_account: Box<Account<'info, TokenAccount>>,
     #[account(
+        mut,
         constraint = initializer_token_account.mint == mint.key(), // USDC Stables
-        constraint = initializer_token_account.owner == service.initializer
+        constraint = initializer_token_account.owner == service_type.initializer
     )]
-    pub initializer_token_account: Account<'info, TokenAccount>,
+    pub initializer_token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: Deposit payer, receives the escrow rent
+    #[account(mut, constraint = initializer.key() == service_type.initializer)]
+    pub initializer: AccountInfo<'info>,
     #[account(mut)]
     pub global_authority: Signer<'info>,
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::*;
+
+use crate::state::*;
+
+// APPEAL DRIVER INFRASTRUCTURE SLASH
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, slash_count: u64)]
+pub struct AppealDriverInfraSlash<'info> {
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
+    #[account(
+        seeds = [b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()],
+        bump,
+        constraint = driver_infra.update_authority == driver_infra_owner.key()
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    pub driver_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &slash_count.to_le_bytes()], bump,
+        constraint = slash_record.infra == driver_infra.key(),
+        constraint = slash_record.infra_type == Actor::DriverInfra,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+}
+// APPEAL CUSTOMER INFRASTRUCTURE SLASH
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, slash_count: u64)]
+pub struct AppealCustomerInfraSlash<'info> {
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
+    #[account(
+        seeds = [b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()],
+        bump,
+        constraint = customer_infra.update_authority == customer_infra_owner.key()
+    )]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    pub customer_infra_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &slash_count.to_le_bytes()], bump,
+        constraint = slash_record.infra == customer_infra.key(),
+        constraint = slash_record.infra_type == Actor::CustomerInfra,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+}
+
+// REVERSE DRIVER INFRASTRUCTURE SLASH, GLOBAL AUTHORITY
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, driver_infra_count: u64, slash_count: u64)]
+pub struct ReverseDriverInfraSlash<'info> {
+    #[account(
+        seeds=[b"global"], bump,
+        constraint = global_state.update_authority == global_authority.key()
+    )]
+    pub global_state: Account<'info, Global>,
+    pub global_authority: Signer<'info>,
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
+    #[account(
+        mut,
+        seeds = [b"driver_infra".as_ref(), alpha3_country_code.as_ref(), &driver_infra_count.to_le_bytes()],
+        bump,
+    )]
+    pub driver_infra: Account<'info, DriverInfra>,
+    #[account(
+        mut,
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &slash_count.to_le_bytes()], bump,
+        constraint = slash_record.infra == driver_infra.key(),
+        constraint = slash_record.infra_type == Actor::DriverInfra,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(
+        mut,
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
+    )]
+    pub country_stable_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = driver_stable_account.mint == mint.key(), // SGD Stables
+        constraint = driver_stable_account.owner == driver_infra.key(),
+    )]
+    pub driver_stable_account: Account<'info, TokenAccount>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
+// REVERSE CUSTOMER INFRASTRUCTURE SLASH, GLOBAL AUTHORITY
+#[derive(Accounts)]
+#[instruction(alpha3_country_code: String, customer_infra_count: u64, slash_count: u64)]
+pub struct ReverseCustomerInfraSlash<'info> {
+    #[account(
+        seeds=[b"global"], bump,
+        constraint = global_state.update_authority == global_authority.key()
+    )]
+    pub global_state: Account<'info, Global>,
+    pub global_authority: Signer<'info>,
+    #[account(seeds=[b"country".as_ref(), alpha3_country_code.as_ref()], bump)]
+    pub country_state: Account<'info, Country>,
+    #[account(
+        mut,
+        seeds = [b"customer_infra".as_ref(), alpha3_country_code.as_ref(), &customer_infra_count.to_le_bytes()],
+        bump,
+    )]
+    pub customer_infra: Account<'info, CustomerInfra>,
+    #[account(
+        mut,
+        seeds = [b"slash_record".as_ref(), country_state.key().as_ref(), &slash_count.to_le_bytes()], bump,
+        constraint = slash_record.infra == customer_infra.key(),
+        constraint = slash_record.infra_type == Actor::CustomerInfra,
+    )]
+    pub slash_record: Account<'info, SlashRecord>,
+    #[account(
+        mut,
+        constraint = country_stable_account.mint == mint.key(), // SGD Stables
+        constraint = country_stable_account.owner == country_state.key(),
+    )]
+    pub country_stable_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = customer_stable_account.mint == mint.key(), // SGD Stables
+        constraint = customer_stable_account.owner == customer_infra.key(),
+    )]
+    pub customer_stable_account: Account<'info, TokenAccount>,
+    #[account(constraint = mint.key() == country_state.stable_mint)]
+    pub mint: Account<'info, Mint>, // SGD Stables
+    pub token_program: Program<'info, Token>,
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token::AssociatedToken, token::*};
 
+use crate::error::ErrorCode;
 use crate::state::*;
 
 // INITIALIZE
@@ -33,37 +34,43 @@ pub struct InitVehicle<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-// APPROVAL
+// APPROVAL OR REJECTION
 #[derive(Accounts)]
-#[instruction(vehicle_count: u64, brand: String, model: String)]
+#[instruction(vehicle_count: u64)]
 pub struct VehicleVerification<'info> {
     #[account(
         seeds=[b"global"], bump,
         constraint = global_state.update_authority == global_authority.key()
     )]
     pub global_state: Account<'info, Global>,
-    #[account(seeds=[b"vehicle".as_ref(), &vehicle_count.to_le_bytes()], bump)]
+    #[account(
+        mut, seeds=[b"vehicle".as_ref(), &vehicle_count.to_le_bytes()], bump,
+        constraint = !vehicle.is_valid @ ErrorCode::CatalogItemAlreadyValid
+    )]
     pub vehicle: Account<'info, Vehicle>,
     #[account(
+        mut,
         constraint = vehicle_escrow_account.mint == mint.key(), // USDC Stables
         constraint = vehicle_escrow_account.owner == vehicle.key()
     )]
     pub vehicle_escrow_account: Account<'info, TokenAccount>,
     #[account(
+        mut,
         constraint = global_token_account.mint == mint.key(), // USDC Stables
         constraint = global_token_account.owner == global_state.key()
     )]
     pub global_token_account: Account<'info, TokenAccount>,
     #[account(
+        mut,
         constraint = initializer_token_account.mint == mint.key(), // USDC Stables
         constraint = initializer_token_account.owner == vehicle.initializer
     )]
     pub initializer_token_account: Account<'info, TokenAccount>,
+    /// CHECK: Deposit payer, receives the escrow rent
+    #[account(mut, constraint = initializer.key() == vehicle.initializer)]
+    pub initializer: AccountInfo<'info>,
     #[account(mut)]
     pub global_authority: Signer<'info>,
     pub mint: Account<'info, Mint>, // SGD Stables
     pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
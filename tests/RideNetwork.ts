@@ -8,24 +8,65 @@ import {
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { RideNetwork } from "../target/types/ride_network";
 import {
+  completeDriverInfraWithdrawal,
   createDriver,
+  driverArrived,
   driverCancelJob,
   driverCompleteJob,
   driverCompleteJobAfterFinialize,
+  driverPickupCustomer,
+  driverReportNoShow,
+  driverSetAvailability,
   initDriverInfra,
+  markStaleDriver,
+  initDriverProfile,
+  requestDriverInfraWithdrawal,
+  topUpDriverInfraDeposit,
+  getDriverInfraStableBalance,
   updateDriverInfraBasisPointTest,
   updateDriverInfraBasisPointTestWrongAuth,
   updateDriverInfraCompanyInfo,
   updateDriverInfraCompanyInfoWithWrongAuth,
   updateDriverLocation,
+  updateDriverProfileCredential,
 } from "./testMod/driverTest";
 import {
   approveCustomerInfra,
   approveDriverInfra,
   initCountryState,
+  liftExpiredDriverInfraSuspension,
+  reinstateCustomerInfra,
+  reinstateDriverInfra,
+  suspendCustomerInfra,
+  suspendDriverInfra,
   updateCountryState,
   updateNewAuthority,
 } from "./testMod/countryTest";
+import {
+  customerCancelJob,
+  customerRateDriver,
+  customerReleaseDriver,
+  customerRequestRide,
+  customerReserveDriver,
+  driverAcceptedJob,
+  initCustomerInfra,
+} from "./testMod/customerTest";
+import { settleJob } from "./testMod/settlementTest";
+import {
+  claimDefaultJudgment,
+  customerRaiseIssue,
+  driverRaiseIssue,
+  driverSubmitDisputeEvidence,
+  resolveDispute,
+} from "./testMod/disputeTest";
+import {
+  appealDriverInfraSlash,
+  driverInfraSlash,
+  reverseDriverInfraSlash,
+} from "./testMod/slashTest";
+import { addService, approveService } from "./testMod/serviceTest";
+import { addPassengerType, approvePassengerType } from "./testMod/passenger";
+import { addVehicle, approveVehicle, rejectVehicle } from "./testMod/vehicle";
 import { v4 as uuidv4 } from "uuid";
 
 const program = anchor.workspace.RideNetwork as Program<RideNetwork>;
@@ -79,15 +120,9 @@ describe("Infra Initialization Test", () => {
   it("Driver Infra Test: init", async () => {
     await initDriverInfra(allAccounts);
   });
-  it("Country Test: Verify driver infra", async () => {
-    await approveDriverInfra(allAccounts);
-  });
   it("Customer Infra Test: init", async () => {
     await initCustomerInfra(allAccounts);
   });
-  it("Country Test: Verify customer infra", async () => {
-    await approveCustomerInfra(allAccounts);
-  });
   it("Service Test: Init a new service", async () => {
     await addService(allAccounts);
   });
@@ -97,12 +132,143 @@ describe("Infra Initialization Test", () => {
   it("Vehicle Test: Init a new vehicle", async () => {
     await addVehicle(allAccounts);
   });
+  it("Fail test: Approve service with wrong authority", async () => {
+    await approveService(allAccounts, new anchor.BN(0), false);
+  });
+  it("Service Test: Approve service", async () => {
+    await approveService(allAccounts, new anchor.BN(0));
+  });
+  it("Passenger Test: Approve passenger type", async () => {
+    await approvePassengerType(allAccounts, new anchor.BN(0));
+  });
+  it("Vehicle Test: Approve vehicle", async () => {
+    await approveVehicle(allAccounts, new anchor.BN(0));
+  });
+  it("Vehicle Test: Reject a second vehicle", async () => {
+    await addVehicle(allAccounts);
+    await rejectVehicle(allAccounts, new anchor.BN(1));
+  });
+  it("Fail test: Start work with unverified driver infra", async () => {
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(
+      allAccounts,
+      extractParts(uuidv4()),
+      publicKeyPem,
+      "InfraNotVerified"
+    );
+  });
+  it("Country Test: Verify driver infra", async () => {
+    await approveDriverInfra(allAccounts);
+  });
+  it("Fail test: Start work with duplicate service IDs", async () => {
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(
+      allAccounts,
+      extractParts(uuidv4()),
+      publicKeyPem,
+      "DuplicateCatalogItem",
+      [new anchor.BN(0), new anchor.BN(0)]
+    );
+  });
+  it("Fail test: Start work with unknown passenger type ID", async () => {
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(
+      allAccounts,
+      extractParts(uuidv4()),
+      publicKeyPem,
+      "InvalidCatalogAccount",
+      [new anchor.BN(0)],
+      [new anchor.BN(99)]
+    );
+  });
+  it("Fail test: Request ride with unverified customer infra", async () => {
+    const uuid = extractParts(uuidv4());
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(allAccounts, uuid, publicKeyPem);
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      uuid,
+      "InfraNotVerified"
+    );
+  });
+  it("Country Test: Verify customer infra", async () => {
+    await approveCustomerInfra(allAccounts);
+  });
+  it("Driver Infra Test: Top up deposit", async () => {
+    await topUpDriverInfraDeposit(allAccounts, new anchor.BN(100_00));
+  });
+  it("Driver Infra Test: Request deposit withdrawal", async () => {
+    await requestDriverInfraWithdrawal(allAccounts, new anchor.BN(100_00));
+  });
+  it("Fail test: Complete withdrawal before unbonding", async () => {
+    await completeDriverInfraWithdrawal(allAccounts, false);
+  });
+  it("Driver Infra Test: Complete withdrawal after unbonding", async () => {
+    await delayScript(2000);
+    await completeDriverInfraWithdrawal(allAccounts);
+  });
+  it("Country Test: Slash driver infra", async () => {
+    await driverInfraSlash(allAccounts, 15_000);
+  });
+  it("Driver Infra Test: Appeal slash", async () => {
+    await appealDriverInfraSlash(allAccounts, new anchor.BN(0));
+  });
+  it("Fail test: Appeal slash twice", async () => {
+    await appealDriverInfraSlash(allAccounts, new anchor.BN(0), false);
+  });
+  it("Fail test: Reverse slash with wrong global authority", async () => {
+    await reverseDriverInfraSlash(allAccounts, new anchor.BN(0), false);
+  });
+  it("Global Test: Reverse slash", async () => {
+    await reverseDriverInfraSlash(allAccounts, new anchor.BN(0));
+  });
 
-  // TODO
-  // it("Country Test: Freeze driver infra", async () => {});
-  // it("Country Test: unfreeze driver infra", async () => {});
-  // it("Country Test: Freeze rider infra", async () => {});
-  // it("Country Test: unfreeze rider infra", async () => {});
+  it("Country Test: Suspend driver infra indefinitely", async () => {
+    await suspendDriverInfra(allAccounts, null);
+  });
+  it("Fail test: Start work with frozen driver infra", async () => {
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(
+      allAccounts,
+      extractParts(uuidv4()),
+      publicKeyPem,
+      "InfraFrozen"
+    );
+  });
+  it("Fail test: Lift indefinite suspension", async () => {
+    await liftExpiredDriverInfraSuspension(allAccounts, "NoTimedSuspension");
+  });
+  it("Country Test: Reinstate driver infra", async () => {
+    await reinstateDriverInfra(allAccounts);
+  });
+  it("Country Test: Suspend driver infra for 1 second", async () => {
+    await suspendDriverInfra(allAccounts, 1);
+  });
+  it("Driver Infra Test: Lift expired suspension", async () => {
+    await delayScript(2000);
+    await liftExpiredDriverInfraSuspension(allAccounts);
+  });
+  it("Country Test: Suspend customer infra indefinitely", async () => {
+    await suspendCustomerInfra(allAccounts, null);
+  });
+  it("Fail test: Request ride with frozen customer infra", async () => {
+    const uuid = extractParts(uuidv4());
+    const { publicKeyPem } = generateRsaKeypair();
+    await createDriver(allAccounts, uuid, publicKeyPem);
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(allAccounts, encryptedData, uuid, "InfraFrozen");
+  });
+  it("Country Test: Reinstate customer infra", async () => {
+    await reinstateCustomerInfra(allAccounts);
+  });
 });
 
 describe("Infra Initialization Fail Test", () => {
@@ -160,6 +326,19 @@ describe("Best Case: No dispute", () => {
   const shortenD1Uuid = extractParts(d1Uuid);
   const { publicKeyPem, privateKeyPem } = generateRsaKeypair();
 
+  it("Fail test: Start work with expired license", async () => {
+    const expired = Math.floor(Date.now() / 1000) - 60;
+    await initDriverProfile(allAccounts, shortenD1Uuid, expired);
+    await createDriver(
+      allAccounts,
+      shortenD1Uuid,
+      publicKeyPem,
+      "DriverCredentialExpired"
+    );
+  });
+  it("Driver Test: Renew driver credentials", async () => {
+    await updateDriverProfileCredential(allAccounts, shortenD1Uuid);
+  });
   it("Driver Test: Start Work", async () => {
     // Require UUID Generation
     await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
@@ -168,6 +347,150 @@ describe("Best Case: No dispute", () => {
   it("Driver Test: Update driver location", async () => {
     await updateDriverLocation(allAccounts, shortenD1Uuid);
   });
+  it("Fail test: Update location with only the longitude out of range", async () => {
+    await updateDriverLocation(
+      allAccounts,
+      shortenD1Uuid,
+      { lat: 1_333_330, long: 180_000_001 },
+      "InvalidCoordinatesValid"
+    );
+  });
+  it("Driver Test: Update location east of 90 degrees longitude", async () => {
+    await updateDriverLocation(allAccounts, shortenD1Uuid, {
+      lat: -33_868_800,
+      long: 151_209_300,
+    });
+  });
+
+  it("Fail test: Request a service the driver does not offer", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "ServiceNotOffered",
+      { service: new anchor.BN(1), passengerTypes: [], passengerCount: 1 }
+    );
+  });
+  it("Fail test: Request more passengers than the driver can seat", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "InvalidPassengerCount",
+      { service: new anchor.BN(0), passengerTypes: [], passengerCount: 5 }
+    );
+  });
+  it("Fail test: Request ride below the rate card fare", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "FareBelowRateCard",
+      undefined,
+      undefined,
+      new anchor.BN(10_50)
+    );
+  });
+  it("Fail test: Request ride with a quote not signed by the driver infra", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "InvalidQuoteSignature",
+      undefined,
+      undefined,
+      undefined,
+      { signer: allAccounts.bozo }
+    );
+  });
+  it("Fail test: Request ride with an expired quote", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "QuoteExpired",
+      undefined,
+      undefined,
+      undefined,
+      { expiry: new anchor.BN(Math.floor(Date.now() / 1000) - 60) }
+    );
+  });
+  it("Driver Test: Take a break", async () => {
+    await driverSetAvailability(allAccounts, shortenD1Uuid, { onBreak: {} });
+  });
+  it("Fail test: Request ride while driver is on break", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "DriverNotOnline"
+    );
+  });
+  it("Fail test: Set driver busy manually", async () => {
+    await driverSetAvailability(
+      allAccounts,
+      shortenD1Uuid,
+      { busy: {} },
+      "InvalidAvailability"
+    );
+  });
+  it("Driver Test: Back online", async () => {
+    await driverSetAvailability(allAccounts, shortenD1Uuid, { online: {} });
+  });
+  it("Fail test: Reserve driver beyond the country hold", async () => {
+    await customerReserveDriver(
+      allAccounts,
+      shortenD1Uuid,
+      new anchor.BN(3_600),
+      "InvalidReservationHold"
+    );
+  });
+  it("Customer Test: Reserve and release driver", async () => {
+    await customerReserveDriver(allAccounts, shortenD1Uuid, new anchor.BN(30));
+    await customerReleaseDriver(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Release a driver without holding the reservation", async () => {
+    await customerReleaseDriver(
+      allAccounts,
+      shortenD1Uuid,
+      "NotReservationHolder"
+    );
+  });
+  it("Customer Test: Reserve driver while user confirms", async () => {
+    await customerReserveDriver(allAccounts, shortenD1Uuid, new anchor.BN(30));
+  });
+  it("Fail test: Extend a live reservation", async () => {
+    await customerReserveDriver(
+      allAccounts,
+      shortenD1Uuid,
+      new anchor.BN(30),
+      "ReservationAlreadyHeld"
+    );
+  });
 
   it("Customer Test: Request Ride", async () => {
     const customerData = {
@@ -176,7 +499,27 @@ describe("Best Case: No dispute", () => {
     };
 
     const encryptedData = customerEncryption(publicKeyPem, customerData);
-    await customerRequestRide(allAccounts, encryptedData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Fail test: Request ride against a booked driver", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "DriverAlreadyBooked"
+    );
+  });
+  it("Fail test: Reserve a booked driver", async () => {
+    await customerReserveDriver(
+      allAccounts,
+      shortenD1Uuid,
+      new anchor.BN(30),
+      "DriverAlreadyBooked"
+    );
   });
   it("Driver Test: Driver accept request", async () => {
     // Get data from on-chain
@@ -199,16 +542,28 @@ describe("Best Case: No dispute", () => {
     // Customer respond to accept request
   });
   it("Driver Test: Arrive at location", async () => {
-    //! Both infra communicate according to protocol
-    // NO NEED TO BE ON-CHAIN
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Arrive at location twice", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid, false);
   });
   it("Driver Test: Pickup rider", async () => {
-    //! Both infra communicate according to protocol
-    // NO NEED TO BE ON-CHAIN
+    await driverPickupCustomer(allAccounts, shortenD1Uuid);
   });
   it("Driver Test: Complete job", async () => {
     await driverCompleteJob(allAccounts, shortenD1Uuid);
   });
+  it("Customer Test: Rate driver", async () => {
+    await customerRateDriver(allAccounts, shortenD1Uuid, 5);
+  });
+  it("Fail test: Rate driver twice", async () => {
+    await customerRateDriver(
+      allAccounts,
+      shortenD1Uuid,
+      4,
+      "DriverAlreadyRated"
+    );
+  });
   it("Driver Infra Test: Process payout after finalize", async () => {
     console.log("Waiting for timeout...");
     await delayScript(2000);
@@ -216,8 +571,175 @@ describe("Best Case: No dispute", () => {
   });
 });
 
+describe("Best Case: Keeper settles job after finalize", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Infra Test: Top up deposit", async () => {
+    await topUpDriverInfraDeposit(allAccounts, new anchor.BN(100_00));
+  });
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const customerData = {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    };
+
+    const encryptedData = customerEncryption(publicKeyPem, customerData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Driver Test: Arrive at location", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Pickup rider", async () => {
+    await driverPickupCustomer(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Complete job", async () => {
+    await driverCompleteJob(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Keeper settle before finalize", async () => {
+    await settleJob(allAccounts, shortenD1Uuid, false);
+  });
+  it("Fail test: Raise issue after finalize", async () => {
+    console.log("Waiting for timeout...");
+    await delayScript(2000);
+    await customerRaiseIssue(
+      allAccounts,
+      shortenD1Uuid,
+      "FinalizeDurationOver"
+    );
+  });
+  it("Keeper Test: Settle job after finalize", async () => {
+    console.log("Waiting for timeout...");
+    await delayScript(2000);
+    await settleJob(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Request withdrawal above the stable balance", async () => {
+    const balance = await getDriverInfraStableBalance(allAccounts);
+    await requestDriverInfraWithdrawal(
+      allAccounts,
+      balance.addn(1),
+      "InsufficientInfraDeposit"
+    );
+  });
+  it("Driver Infra Test: Withdraw deposit together with ride earnings", async () => {
+    // Payout landed after the top up, the whole stable balance is withdrawable
+    const balance = await getDriverInfraStableBalance(allAccounts);
+    await requestDriverInfraWithdrawal(allAccounts, balance);
+    await delayScript(2000);
+    await completeDriverInfraWithdrawal(allAccounts);
+  });
+  it("Driver Infra Test: Restore deposit", async () => {
+    await topUpDriverInfraDeposit(allAccounts, new anchor.BN(10_000_00));
+  });
+});
+
 // SUCESS CASE COMPLETE
 
+describe("Frozen infra cannot move a job forward", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Fail test: Reserve driver with frozen customer infra", async () => {
+    await suspendCustomerInfra(allAccounts, null);
+    await customerReserveDriver(
+      allAccounts,
+      shortenD1Uuid,
+      new anchor.BN(30),
+      "InfraFrozen"
+    );
+    await reinstateCustomerInfra(allAccounts);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Fail test: Accept with frozen driver infra", async () => {
+    await suspendDriverInfra(allAccounts, null);
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid,
+      "InfraFrozen"
+    );
+    await reinstateDriverInfra(allAccounts);
+  });
+  it("Fail test: Accept with frozen customer infra", async () => {
+    await suspendCustomerInfra(allAccounts, null);
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid,
+      "InfraFrozen"
+    );
+    await reinstateCustomerInfra(allAccounts);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Fail test: Arrive with frozen driver infra", async () => {
+    await suspendDriverInfra(allAccounts, null);
+    await driverArrived(allAccounts, shortenD1Uuid, false, "InfraFrozen");
+    await reinstateDriverInfra(allAccounts);
+  });
+  it("Driver Test: Arrive at location", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Pickup with frozen driver infra", async () => {
+    await suspendDriverInfra(allAccounts, null);
+    await driverPickupCustomer(allAccounts, shortenD1Uuid, "InfraFrozen");
+    await reinstateDriverInfra(allAccounts);
+  });
+  it("Fail test: Pickup with frozen customer infra", async () => {
+    await suspendCustomerInfra(allAccounts, null);
+    await driverPickupCustomer(allAccounts, shortenD1Uuid, "InfraFrozen");
+    await reinstateCustomerInfra(allAccounts);
+  });
+  it("Driver Test: Pickup rider", async () => {
+    await driverPickupCustomer(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Complete job", async () => {
+    await driverCompleteJob(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Request ride replaying a used quote nonce", async () => {
+    const encryptedData = customerEncryption(publicKeyPem, {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    });
+    await customerRequestRide(
+      allAccounts,
+      encryptedData,
+      shortenD1Uuid,
+      "QuoteNonceUsed",
+      undefined,
+      undefined,
+      undefined,
+      { nonce: new anchor.BN(1) }
+    );
+  });
+});
+
 describe("Disruption Case: Driver reject call", () => {
   const d1Uuid = uuidv4();
   const shortenD1Uuid = extractParts(d1Uuid);
@@ -239,7 +761,7 @@ describe("Disruption Case: Driver reject call", () => {
     };
 
     const encryptedData = customerEncryption(publicKeyPem, customerData);
-    await customerRequestRide(allAccounts, encryptedData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
   });
 
   it("Driver reject request", async () => {
@@ -271,7 +793,7 @@ describe("Disruption Case: Customer cancel call before driver can accept, before
     };
 
     const encryptedData = customerEncryption(publicKeyPem, customerData);
-    await customerRequestRide(allAccounts, encryptedData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
   });
 
   it("Customer cancel call before driver accept", async () => {
@@ -288,17 +810,66 @@ describe("Disruption Case: Customer cancel call before driver can accept, before
 });
 
 // TODO: Check if fee was deducted
-describe("Disruption Case: Customer cancel call after cancel limit", () => {});
+describe("Disruption Case: Customer cancel call after driver misses pickup ETA", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const d2Uuid = uuidv4();
+  const shortenD2Uuid = extractParts(d2Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+    await createDriver(allAccounts, shortenD2Uuid, publicKeyPem);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const customerData = {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    };
+
+    const encryptedData = customerEncryption(publicKeyPem, customerData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Fail test: Cancel the job passing another driver", async () => {
+    await customerCancelJob(
+      allAccounts,
+      shortenD1Uuid,
+      true,
+      undefined,
+      shortenD2Uuid
+    );
+  });
+  it("Customer cancel after the pickup ETA, driver infra pays", async () => {
+    // Driver starts at the pickup point, the ETA is the acceptance time
+    await delayScript(2000);
+    await customerCancelJob(
+      allAccounts,
+      shortenD1Uuid,
+      true,
+      new anchor.BN(500)
+    );
+  });
+});
 
 // TODO: Check if fee was deducted
 describe("Disruption Case: Driver cancel call after accepting, but before cancel limit", () => {
   const d1Uuid = uuidv4();
   const shortenD1Uuid = extractParts(d1Uuid);
+  const d2Uuid = uuidv4();
+  const shortenD2Uuid = extractParts(d2Uuid);
   const { publicKeyPem, privateKeyPem } = generateRsaKeypair();
 
   it("Driver Test: Start Work", async () => {
     // Require UUID Generation
     await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+    await createDriver(allAccounts, shortenD2Uuid, publicKeyPem);
   });
 
   it("Driver Test: Update driver location", async () => {
@@ -312,7 +883,7 @@ describe("Disruption Case: Driver cancel call after accepting, but before cancel
     };
 
     const encryptedData = customerEncryption(publicKeyPem, customerData);
-    await customerRequestRide(allAccounts, encryptedData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
   });
 
   it("Driver accept request", async () => {
@@ -331,20 +902,189 @@ describe("Disruption Case: Driver cancel call after accepting, but before cancel
       shortenD1Uuid
     );
   });
+  it("Fail test: Go offline with a job in flight", async () => {
+    await driverSetAvailability(
+      allAccounts,
+      shortenD1Uuid,
+      { offline: {} },
+      "DriverHasActiveJob"
+    );
+  });
+  it("Country Test: Suspend driver infra with job in flight", async () => {
+    await suspendDriverInfra(allAccounts, null);
+  });
+  it("Fail test: Arrive at location with frozen driver infra", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid, false, "InfraFrozen");
+  });
+  it("Fail test: Cancel the job passing another driver", async () => {
+    await driverCancelJob(allAccounts, shortenD1Uuid, true, shortenD2Uuid);
+  });
   it("Driver cancel request after accepting", async () => {
+    // Frozen infra can still wind down the job
     await driverCancelJob(allAccounts, shortenD1Uuid);
   });
+  it("Country Test: Reinstate driver infra", async () => {
+    await reinstateDriverInfra(allAccounts);
+  });
+  it("Driver Test: Go offline after cancelling", async () => {
+    await driverSetAvailability(allAccounts, shortenD1Uuid, { offline: {} });
+  });
 });
 describe("Disruption Case: Driver cancel call after cancel limit", () => {});
-describe("Disruption Case: Driver arrive at pickup point, customer late", () => {});
-describe("Disruption Case: Driver arrive at destination, customer raise dispute", () => {});
-describe("Disruption Case: Driver arrive at destination, driver raise dispute", () => {});
+describe("Disruption Case: Driver loses connectivity", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Fail test: Mark a live driver stale", async () => {
+    await markStaleDriver(allAccounts, shortenD1Uuid, "DriverNotStale");
+  });
+  it("Keeper Test: Mark stale driver after heartbeat timeout", async () => {
+    console.log("Waiting for heartbeat timeout...");
+    await delayScript(3000);
+    await markStaleDriver(allAccounts, shortenD1Uuid);
+  });
+});
+describe("Disruption Case: Driver arrive at pickup point, customer late", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const customerData = {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    };
+
+    const encryptedData = customerEncryption(publicKeyPem, customerData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Fail test: Report no-show before arriving", async () => {
+    await driverReportNoShow(
+      allAccounts,
+      shortenD1Uuid,
+      "InvalidStatusTransition"
+    );
+  });
+  it("Driver Test: Arrive at location", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Report no-show within the no-show wait", async () => {
+    await driverReportNoShow(allAccounts, shortenD1Uuid, "NoShowWaitNotPassed");
+  });
+  it("Driver Test: Report no-show after the no-show wait", async () => {
+    console.log("Waiting for no-show wait...");
+    await delayScript(3000);
+    await driverReportNoShow(allAccounts, shortenD1Uuid);
+  });
+});
+describe("Disruption Case: Driver arrive at destination, customer raise dispute", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const customerData = {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    };
+
+    const encryptedData = customerEncryption(publicKeyPem, customerData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Driver Test: Arrive at location", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Pickup rider", async () => {
+    await driverPickupCustomer(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Complete job", async () => {
+    await driverCompleteJob(allAccounts, shortenD1Uuid);
+  });
+  it("Customer Test: Raise issue", async () => {
+    await customerRaiseIssue(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Submit evidence w wrong auth", async () => {
+    await driverSubmitDisputeEvidence(allAccounts, shortenD1Uuid, false);
+  });
+  it("Driver Test: Respond with evidence", async () => {
+    await driverSubmitDisputeEvidence(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Resolve dispute w wrong auth", async () => {
+    await resolveDispute(allAccounts, shortenD1Uuid, 2_500, false);
+  });
+  it("Country Test: Resolve dispute with partial split", async () => {
+    await resolveDispute(allAccounts, shortenD1Uuid, 2_500);
+  });
+});
+describe("Disruption Case: Driver arrive at destination, driver raise dispute", () => {
+  const d1Uuid = uuidv4();
+  const shortenD1Uuid = extractParts(d1Uuid);
+  const { publicKeyPem } = generateRsaKeypair();
+
+  it("Driver Test: Start Work", async () => {
+    await createDriver(allAccounts, shortenD1Uuid, publicKeyPem);
+  });
+  it("Customer Test: Request Ride", async () => {
+    const customerData = {
+      endpoint: "https://helloWorldThisIsATestWebsite.com.sg/request-rider/XX", // 60
+      apiUuidV4: "753d36f5-a272-4129-8eecXX", // 25
+    };
+
+    const encryptedData = customerEncryption(publicKeyPem, customerData);
+    await customerRequestRide(allAccounts, encryptedData, shortenD1Uuid);
+  });
+  it("Driver Test: Driver accept request", async () => {
+    await driverAcceptedJob(
+      allAccounts,
+      allAccounts.driverInfraOwner.publicKey,
+      shortenD1Uuid
+    );
+  });
+  it("Driver Test: Arrive at location", async () => {
+    await driverArrived(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Pickup rider", async () => {
+    await driverPickupCustomer(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Complete job", async () => {
+    await driverCompleteJob(allAccounts, shortenD1Uuid);
+  });
+  it("Driver Test: Raise issue", async () => {
+    await driverRaiseIssue(allAccounts, shortenD1Uuid);
+  });
+  it("Fail test: Default judgment before dispute waitout", async () => {
+    await claimDefaultJudgment(allAccounts, shortenD1Uuid, false);
+  });
+});
 // ------------------------
 // Driver receive 2 jobs, accept 1st call
 // Should reject 2nd call
 // ------------------------
 // TODO: distribution size > 3 (2x Platform Fees)
-// TODO: Settlement logic, Slashing amount
 // TODO: Settlement logic, DAO voting (Infra NFT)
 // TODO: Test Upgrade, create assert function to test entire state
 
@@ -31,16 +31,6 @@ export const initOrUpdateCountryAssert = async (
     +params.waitingFeeCent,
     "waitingFeeCent"
   );
-  assert.equal(
-    +countryAccount.driverCancellationFeeSec,
-    +params.driverCancellationFeeSec,
-    "driverCancellationFeeSec"
-  );
-  assert.equal(
-    +countryAccount.customerCancellationFeeSec,
-    +params.customerCancellationFeeSec,
-    "customerCancellationFeeSec"
-  );
   assert.equal(
     +countryAccount.baseRateCent,
     +params.baseRateCent,
@@ -76,5 +66,76 @@ export const initOrUpdateCountryAssert = async (
     +params.baseSlashAmount,
     "baseSlashAmount"
   );
+  assert.equal(
+    +countryAccount.keeperRewardCent,
+    +params.keeperRewardCent,
+    "keeperRewardCent"
+  );
+  assert.equal(
+    +countryAccount.infraUnbondingPeriodSec,
+    +params.infraUnbondingPeriodSec,
+    "infraUnbondingPeriodSec"
+  );
+  assert.equal(
+    +countryAccount.depositGracePeriodSec,
+    +params.depositGracePeriodSec,
+    "depositGracePeriodSec"
+  );
+  assert.equal(
+    +countryAccount.slashAppealPeriodSec,
+    +params.slashAppealPeriodSec,
+    "slashAppealPeriodSec"
+  );
+  assert.equal(
+    countryAccount.minReputationBasisPoint,
+    params.minReputationBasisPoint,
+    "minReputationBasisPoint"
+  );
+  assert.equal(
+    +countryAccount.reputationMinMatchedRide,
+    +params.reputationMinMatchedRide,
+    "reputationMinMatchedRide"
+  );
+  assert.deepEqual(
+    countryAccount.depositTiers,
+    params.depositTiers,
+    "depositTiers"
+  );
+  assert.equal(
+    +countryAccount.maxReservationHoldSec,
+    +params.maxReservationHoldSec,
+    "maxReservationHoldSec"
+  );
+  assert.equal(
+    +countryAccount.driverHeartbeatTimeoutSec,
+    +params.driverHeartbeatTimeoutSec,
+    "driverHeartbeatTimeoutSec"
+  );
+  assert.equal(
+    +countryAccount.maxWaitingFeeCent,
+    +params.maxWaitingFeeCent,
+    "maxWaitingFeeCent"
+  );
+  assert.equal(
+    +countryAccount.noShowWaitSec,
+    +params.noShowWaitSec,
+    "noShowWaitSec"
+  );
+  assert.equal(
+    +countryAccount.noShowFeeCent,
+    +params.noShowFeeCent,
+    "noShowFeeCent"
+  );
+  // BN fields compare by value once serialized
+  assert.equal(
+    JSON.stringify(countryAccount.cancellationTiers),
+    JSON.stringify(params.cancellationTiers),
+    "cancellationTiers"
+  );
+  assert.equal(
+    +countryAccount.pickupSpeedMPerSec,
+    +params.pickupSpeedMPerSec,
+    "pickupSpeedMPerSec"
+  );
   assert.equal(countryAccount.isInitialized, true);
 };
@@ -21,6 +21,7 @@ import {
   IUpdateInfraAssert,
   CustomerData,
   EncryptedData,
+  RideRequirements,
 } from "../utils/types";
 
 export const initCustomerInfraAssert = async (
@@ -186,6 +187,7 @@ export const customerRequestRideAssert = async (
   driverInfraCount: anchor.BN,
   encryptedData: EncryptedData,
   calculatedFees: anchor.BN,
+  rideRequirements: RideRequirements,
   allAccounts: IAllAccounts
 ) => {
   const customerInfraAddress = getCustomerInfraAddress(
@@ -215,4 +217,51 @@ export const customerRequestRideAssert = async (
     encryptedData.encryptedData,
     "encryptedData"
   );
+  expect(+jobData.service).to.equal(+rideRequirements.service, "service");
+  expect(jobData.passengerTypes.map((id) => +id)).to.deep.equal(
+    rideRequirements.passengerTypes.map((id) => +id),
+    "passengerTypes"
+  );
+  expect(jobData.passengerCount).to.equal(
+    rideRequirements.passengerCount,
+    "passengerCount"
+  );
+  const driverData = await getDriverData(program, driverUuid);
+  expect(driverData.activeJob?.toString()).to.equal(
+    jobDataX.publicKey.toString(),
+    "activeJob"
+  );
+  expect(driverData.reservedBy).to.equal(null, "reservedBy");
+  expect(jobData.quoteHash.some((byte) => byte !== 0)).to.equal(
+    true,
+    "quoteHash"
+  );
+  const driverProfileData = await program.account.driverProfile.fetch(
+    driverData.profile
+  );
+  expect(+driverProfileData.quoteNonce).to.be.greaterThan(0, "quoteNonce");
+  // Terms are a copy of the country at request time
+  const countryData = await getCountryData(program, "SGP");
+  const terms = jobData.terms;
+  expect(+terms.baseRateCent).to.equal(+countryData.baseRateCent, "baseRate");
+  expect(+terms.waitingFeeCent).to.equal(
+    +countryData.waitingFeeCent,
+    "waitingFeeCent"
+  );
+  expect(+terms.noShowFeeCent).to.equal(
+    +countryData.noShowFeeCent,
+    "noShowFeeCent"
+  );
+  expect(+terms.finalizeDurationSec).to.equal(
+    +countryData.finalizeDurationSec,
+    "finalizeDurationSec"
+  );
+  expect(+terms.keeperRewardCent).to.equal(
+    +countryData.keeperRewardCent,
+    "keeperRewardCent"
+  );
+  expect(JSON.stringify(terms.cancellationTiers)).to.equal(
+    JSON.stringify(countryData.cancellationTiers),
+    "cancellationTiers"
+  );
 };
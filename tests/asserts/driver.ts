@@ -2,7 +2,7 @@ import * as anchor from "@project-serum/anchor";
 import { PublicKey } from "@solana/web3.js";
 import { expect } from "chai";
 import { RideNetwork } from "../../target/types/ride_network";
-import {} from "../utils/helperFn";
+import { computeFare } from "../utils/helperFn";
 import {
   getCompanyData,
   getCountryData,
@@ -43,6 +43,11 @@ export const initDriverInfraAssert = async (
     params.driverInfraFeeBasisPoint,
     "driverInfraFeeBasisPoint"
   );
+  expect(+driverInfraData.openJobs).to.equal(0, "openJobs");
+  expect(driverInfraData.reputationBasisPoint).to.equal(
+    10_000,
+    "reputationBasisPoint"
+  );
   expect(companyData.companyName).to.equal(params.companyName, "companyName");
   expect(companyData.entityRegistryId).to.equal(
     params.entityRegistryId,
@@ -109,6 +114,10 @@ export const createDriverAssert = async (
     "currentLocation: Long"
   );
   expect(driverData.nextLocation).to.equal(null, "nextLocation");
+  expect(driverData.availability).to.deep.equal(
+    { online: {} },
+    "availability"
+  );
 };
 
 export const updateDriverLocationAssert = async (
@@ -148,9 +157,39 @@ export const assertDriverCompleteJob = async (
   customerInfraPda: PublicKey
 ) => {
   const jobData = await getJobData(program, customerInfraPda, driverUuid);
+  // Final fare is the rate card on the metered trip
+  const finalFare = computeFare(
+    jobData.account.terms,
+    jobData.account.tripDistanceM,
+    jobData.account.jobEndTime.sub(jobData.account.pickupTime)
+  );
   // ASSERT TEST
   expect(Object.keys(jobData.account.status)[0]).to.equal(
     "completed",
     "status"
   );
+  expect(+jobData.account.totalFeeCent).to.equal(+finalFare, "totalFeeCent");
+};
+
+export const assertDriverArrived = async (
+  program: anchor.Program<RideNetwork>,
+  driverUuid: string,
+  customerInfraPda: PublicKey
+) => {
+  const jobData = await getJobData(program, customerInfraPda, driverUuid);
+  // ASSERT TEST
+  expect(Object.keys(jobData.account.status)[0]).to.equal("arrived", "status");
+  expect(jobData.account.arrivalTime).to.not.equal(null, "arrivalTime");
+};
+
+export const assertDriverPickupCustomer = async (
+  program: anchor.Program<RideNetwork>,
+  driverUuid: string,
+  customerInfraPda: PublicKey
+) => {
+  const jobData = await getJobData(program, customerInfraPda, driverUuid);
+  // ASSERT TEST
+  expect(Object.keys(jobData.account.status)[0]).to.equal("started", "status");
+  // Picked up within the free waiting time
+  expect(+jobData.account.waitingFeeCent).to.equal(0, "waitingFeeCent");
 };
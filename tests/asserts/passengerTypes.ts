@@ -28,6 +28,7 @@ export const assertNewPassengerType = async (
   );
   assert.equal(passengerTypeData.name, name, "name");
   assert.equal(passengerTypeData.isValid, false, "isValid");
+  assert.equal(passengerTypeData.isRejected, false, "isRejected");
   assert.equal(passengerTypeData.isInitialized, true, "isInitialized");
   assert.equal(
     passengerTypeData.initializer.toString(),
@@ -22,6 +22,7 @@ export const assertNewService = async (
   assert.equal(serviceData.name, name, "name");
   assert.equal(+serviceData.serviceCount, +serviceCount, "serviceCount");
   assert.equal(serviceData.isValid, false, "isValid");
+  assert.equal(serviceData.isRejected, false, "isRejected");
   assert.equal(serviceData.isInitialized, true, "isInitialized");
   assert.equal(
     serviceData.initializer.toString(),
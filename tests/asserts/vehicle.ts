@@ -18,6 +18,7 @@ export const assertNewVehicle = async (
   assert.equal(vehicleData.model, model, "model");
   assert.equal(vehicleData.numberOfSeats, numberOfSeats, "numberOfSeats");
   assert.equal(vehicleData.isValid, false, "isValid");
+  assert.equal(vehicleData.isRejected, false, "isRejected");
   assert.equal(
     vehicleData.initializer.toString(),
     initializer.toString(),
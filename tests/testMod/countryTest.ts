@@ -13,9 +13,12 @@ import {
   getCountryAddress,
   getCountryData,
   getCustomerInfraAddress,
+  getCustomerInfraData,
   getDriverAddress,
   getDriverData,
   getDriverInfraAddress,
+  getDriverInfraData,
+  getGlobalAddress,
 } from "../utils/pda";
 import { IAllAccounts } from "../utils/types";
 import { assertVerifyDriverInfra } from "../asserts/driver";
@@ -33,14 +36,17 @@ export const initCountryState = async (
     countryPda,
     true
   );
+  const globalPda = getGlobalAddress(program);
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalPda,
+    true
+  );
 
   let params = {
     platformFeeBasisPoint: 100,
     waitingFeeSec: new anchor.BN(300),
-    waitingFeeCent: new anchor.BN(5000),
-    driverCancellationFeeSec: new anchor.BN(300),
-    customerCancellationFeeSec: new anchor.BN(300),
-    cancellationFeeCent: new anchor.BN(5000),
+    waitingFeeCent: new anchor.BN(30),
     baseRateCent: new anchor.BN(4500),
     minKmRateCent: new anchor.BN(20),
     minMinFeeCent: new anchor.BN(10),
@@ -49,6 +55,35 @@ export const initCountryState = async (
     minCustomerInfraDeposit: new anchor.BN(10_000_00),
     disputeWaitoutPeriod: new anchor.BN(10_000_00),
     baseSlashAmount: new anchor.BN(10_00),
+    keeperRewardCent: new anchor.BN(10),
+    infraUnbondingPeriodSec: new anchor.BN(1),
+    depositGracePeriodSec: new anchor.BN(86_400),
+    slashAppealPeriodSec: new anchor.BN(86_400),
+    minReputationBasisPoint: 5_000,
+    reputationMinMatchedRide: new anchor.BN(10),
+    depositTiers: [
+      { belowReputationBasisPoint: 8_000, depositMultiplierBasisPoint: 15_000 },
+    ],
+    maxReservationHoldSec: new anchor.BN(60),
+    driverHeartbeatTimeoutSec: new anchor.BN(60),
+    maxWaitingFeeCent: new anchor.BN(900),
+    noShowWaitSec: new anchor.BN(300),
+    noShowFeeCent: new anchor.BN(500),
+    cancellationTiers: [
+      {
+        stage: { afterAcceptance: {} },
+        cancelledBy: { customerInfra: {} },
+        afterSec: new anchor.BN(300),
+        charge: { flatCent: { 0: new anchor.BN(500) } },
+      },
+      {
+        stage: { afterAcceptance: {} },
+        cancelledBy: { driverInfra: {} },
+        afterSec: new anchor.BN(300),
+        charge: { flatCent: { 0: new anchor.BN(500) } },
+      },
+    ],
+    pickupSpeedMPerSec: new anchor.BN(8),
   };
 
   try {
@@ -58,6 +93,8 @@ export const initCountryState = async (
         countryState: countryPda,
         authority: mainWallet2.publicKey,
         countryStableAccount: countryStableAta,
+        globalState: globalPda,
+        globalStableAccount: globalStableAta,
         mint: stableMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -90,14 +127,17 @@ export const updateCountryState = async (
     countryPda,
     true
   );
+  const globalPda = getGlobalAddress(program);
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalPda,
+    true
+  );
 
   const params = {
     platformFeeBasisPoint: 200,
     waitingFeeSec: new anchor.BN(300),
-    waitingFeeCent: new anchor.BN(5000),
-    driverCancellationFeeSec: new anchor.BN(300),
-    customerCancellationFeeSec: new anchor.BN(300),
-    cancellationFeeCent: new anchor.BN(5000),
+    waitingFeeCent: new anchor.BN(30),
     baseRateCent: new anchor.BN(4000),
     minKmRateCent: new anchor.BN(10),
     minMinFeeCent: new anchor.BN(15),
@@ -106,6 +146,47 @@ export const updateCountryState = async (
     minCustomerInfraDeposit: new anchor.BN(1_000_00),
     disputeWaitoutPeriod: new anchor.BN(1000000),
     baseSlashAmount: new anchor.BN(100),
+    keeperRewardCent: new anchor.BN(10),
+    infraUnbondingPeriodSec: new anchor.BN(1),
+    depositGracePeriodSec: new anchor.BN(86_400),
+    slashAppealPeriodSec: new anchor.BN(86_400),
+    minReputationBasisPoint: 5_000,
+    reputationMinMatchedRide: new anchor.BN(10),
+    depositTiers: [
+      { belowReputationBasisPoint: 8_000, depositMultiplierBasisPoint: 15_000 },
+    ],
+    maxReservationHoldSec: new anchor.BN(60),
+    driverHeartbeatTimeoutSec: new anchor.BN(2),
+    maxWaitingFeeCent: new anchor.BN(900),
+    noShowWaitSec: new anchor.BN(2),
+    noShowFeeCent: new anchor.BN(500),
+    cancellationTiers: [
+      {
+        stage: { afterAcceptance: {} },
+        cancelledBy: { customerInfra: {} },
+        afterSec: new anchor.BN(300),
+        charge: { flatCent: { 0: new anchor.BN(500) } },
+      },
+      {
+        stage: { afterArrival: {} },
+        cancelledBy: { customerInfra: {} },
+        afterSec: new anchor.BN(120),
+        charge: { fareBasisPoint: { 0: 1_000 } },
+      },
+      {
+        stage: { etaBreach: {} },
+        cancelledBy: { customerInfra: {} },
+        afterSec: new anchor.BN(0),
+        charge: { flatCent: { 0: new anchor.BN(500) } },
+      },
+      {
+        stage: { afterAcceptance: {} },
+        cancelledBy: { driverInfra: {} },
+        afterSec: new anchor.BN(300),
+        charge: { flatCent: { 0: new anchor.BN(500) } },
+      },
+    ],
+    pickupSpeedMPerSec: new anchor.BN(8),
   };
 
   try {
@@ -115,6 +196,8 @@ export const updateCountryState = async (
         countryState: countryPda,
         authority: fakeAuth ? bozo.publicKey : mainWallet2.publicKey,
         countryStableAccount: countryStableAta,
+        globalState: globalPda,
+        globalStableAccount: globalStableAta,
         mint: stableMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
@@ -252,3 +335,148 @@ export const approveCustomerInfra = async (
     }
   }
 };
+
+export const suspendDriverInfra = async (
+  allAccounts: IAllAccounts,
+  durationSec: number | null
+) => {
+  const { program, mainWallet1 } = allAccounts;
+  const targetDriver = (await getAllDriverInfraData(program))[0];
+  const driverInfraCount = targetDriver.account.driverInfraCount;
+
+  await program.methods
+    .driverInfraSuspension(
+      "SGP",
+      driverInfraCount,
+      { misconduct: {} },
+      durationSec === null ? null : new anchor.BN(durationSec)
+    )
+    .accounts({
+      countryState: getCountryAddress(program, "SGP"),
+      countryAuthority: mainWallet1.publicKey,
+      driverInfra: getDriverInfraAddress(program, driverInfraCount),
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const driverInfraData = await getDriverInfraData(program, driverInfraCount);
+  expect(driverInfraData.isFrozen).to.equal(true, "isFrozen");
+  assert.deepEqual(driverInfraData.suspensionReason, { misconduct: {} });
+  assert.equal(
+    driverInfraData.suspendedUntil === null,
+    durationSec === null,
+    "suspendedUntil"
+  );
+};
+
+export const suspendCustomerInfra = async (
+  allAccounts: IAllAccounts,
+  durationSec: number | null
+) => {
+  const { program, mainWallet1 } = allAccounts;
+  const targetCustomerInfra = (await getAllCustomerInfraData(program))[0];
+  const customerInfraCount = targetCustomerInfra.account.customerInfraCount;
+
+  await program.methods
+    .customerInfraSuspension(
+      "SGP",
+      customerInfraCount,
+      { compliance: {} },
+      durationSec === null ? null : new anchor.BN(durationSec)
+    )
+    .accounts({
+      countryState: getCountryAddress(program, "SGP"),
+      countryAuthority: mainWallet1.publicKey,
+      customerInfra: getCustomerInfraAddress(program, customerInfraCount),
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const customerInfraData = await getCustomerInfraData(
+    program,
+    customerInfraCount
+  );
+  expect(customerInfraData.isFrozen).to.equal(true, "isFrozen");
+  assert.deepEqual(customerInfraData.suspensionReason, { compliance: {} });
+};
+
+export const reinstateDriverInfra = async (allAccounts: IAllAccounts) => {
+  const { program, mainWallet1 } = allAccounts;
+  const targetDriver = (await getAllDriverInfraData(program))[0];
+  const driverInfraCount = targetDriver.account.driverInfraCount;
+
+  await program.methods
+    .reinstateDriverInfra("SGP", driverInfraCount)
+    .accounts({
+      countryState: getCountryAddress(program, "SGP"),
+      countryAuthority: mainWallet1.publicKey,
+      driverInfra: getDriverInfraAddress(program, driverInfraCount),
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const driverInfraData = await getDriverInfraData(program, driverInfraCount);
+  expect(driverInfraData.isFrozen).to.equal(false, "isFrozen");
+  assert.equal(driverInfraData.suspensionReason, null, "suspensionReason");
+};
+
+export const reinstateCustomerInfra = async (allAccounts: IAllAccounts) => {
+  const { program, mainWallet1 } = allAccounts;
+  const targetCustomerInfra = (await getAllCustomerInfraData(program))[0];
+  const customerInfraCount = targetCustomerInfra.account.customerInfraCount;
+
+  await program.methods
+    .reinstateCustomerInfra("SGP", customerInfraCount)
+    .accounts({
+      countryState: getCountryAddress(program, "SGP"),
+      countryAuthority: mainWallet1.publicKey,
+      customerInfra: getCustomerInfraAddress(program, customerInfraCount),
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const customerInfraData = await getCustomerInfraData(
+    program,
+    customerInfraCount
+  );
+  expect(customerInfraData.isFrozen).to.equal(false, "isFrozen");
+  assert.equal(customerInfraData.suspensionReason, null, "suspensionReason");
+};
+
+export const liftExpiredDriverInfraSuspension = async (
+  allAccounts: IAllAccounts,
+  expectedError?: string
+) => {
+  const { program, bozo } = allAccounts;
+  const targetDriver = (await getAllDriverInfraData(program))[0];
+  const driverInfraCount = targetDriver.account.driverInfraCount;
+
+  try {
+    await program.methods
+      .liftExpiredDriverInfraSuspension("SGP", driverInfraCount)
+      .accounts({
+        driverInfra: getDriverInfraAddress(program, driverInfraCount),
+        caller: bozo.publicKey,
+      })
+      .signers([bozo])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Lift should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const driverInfraData = await getDriverInfraData(program, driverInfraCount);
+  expect(driverInfraData.isFrozen).to.equal(false, "isFrozen");
+  assert.equal(driverInfraData.suspendedUntil, null, "suspendedUntil");
+};
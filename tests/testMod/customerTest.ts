@@ -1,6 +1,7 @@
 import * as anchor from "@project-serum/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAccount,
   getAssociatedTokenAddress,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
@@ -24,13 +25,30 @@ import {
   getGlobalAddress,
   getDriverInfraDataByUpdateAuthority,
   getDriverAddress,
+  getDriverData,
   getDriverInfraAddress,
+  getDriverProfileAddress,
   getJobData,
   getJobDataByAddress,
 } from "../utils/pda";
-import { EncryptedData, IAllAccounts } from "../utils/types";
+import {
+  EncryptedData,
+  IAllAccounts,
+  QuoteOverride,
+  RideRequirements,
+  TripEstimate,
+} from "../utils/types";
+import {
+  computeFare,
+  driverQuoteMessage,
+  getEvents,
+} from "../utils/helperFn";
 import { customerEncryption } from "../utils/encryption";
-import { PublicKey } from "@solana/web3.js";
+import {
+  Ed25519Program,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
 
 export const initCustomerInfra = async (
   allAccounts: IAllAccounts,
@@ -343,11 +361,27 @@ export const updateCustomerInfraBasisPointTestWrongAuth = async (
 
 export const customerRequestRide = async (
   allAccounts: IAllAccounts,
-  encryptedData: EncryptedData
+  encryptedData: EncryptedData,
+  selectedDriverUuid?: string,
+  expectedError?: string,
+  rideRequirements: RideRequirements = {
+    service: new anchor.BN(0),
+    passengerTypes: [new anchor.BN(0)],
+    passengerCount: 1,
+  },
+  trip: TripEstimate = {
+    distanceM: new anchor.BN(5_000),
+    durationSec: new anchor.BN(600),
+  },
+  totalFeesOverride?: anchor.BN,
+  quoteOverride: QuoteOverride = {}
 ) => {
-  const { program, customerInfraOwner, stableMint } = allAccounts;
+  const { program, customerInfraOwner, driverInfraOwner, stableMint } =
+    allAccounts;
   const globalState = getGlobalAddress(program);
-  const selectedDriver = await getAllDriver(program);
+  const selectedDriver = selectedDriverUuid
+    ? { account: await getDriverData(program, selectedDriverUuid) }
+    : await getAllDriver(program);
   const driverUuid = selectedDriver.account.driverUuid;
   const driverInfraAddress = selectedDriver.account.infraAuthority;
   const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
@@ -373,8 +407,30 @@ export const customerRequestRide = async (
     jobPda,
     true
   );
-  const distributionLen = 2;
-  const totalFees = new anchor.BN(10_50);
+  // Driver infra, customer infra, country and global treasuries
+  const distributionLen = 4;
+  // Pay exactly the rate card unless a test overrides it
+  const countryData = await getCountryData(program);
+  const totalFees =
+    totalFeesOverride ??
+    computeFare(countryData, trip.distanceM, trip.durationSec);
+
+  // Driver infra signs the fare off-chain, verified by the ed25519 program
+  const quoteExpiry =
+    quoteOverride.expiry ?? new anchor.BN(Math.floor(Date.now() / 1000) + 60);
+  const quoteNonce = quoteOverride.nonce ?? new anchor.BN(Date.now());
+  const driverQuote = {
+    driverInfra: driverInfraAddress,
+    driverUuid,
+    service: rideRequirements.service,
+    fareCent: totalFees,
+    expiry: quoteExpiry,
+    nonce: quoteNonce,
+  };
+  const quoteSignature = Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: (quoteOverride.signer ?? driverInfraOwner).secretKey,
+    message: driverQuoteMessage(driverQuote),
+  });
 
   try {
     await program.methods
@@ -387,23 +443,45 @@ export const customerRequestRide = async (
         distributionLen,
         encryptedData.encryptedData,
         encryptedData.encryptedCombinedRandBase64,
-        totalFees
+        driverQuote,
+        {
+          passengerTypes: rideRequirements.passengerTypes,
+          passengerCount: rideRequirements.passengerCount,
+          tripDistanceM: trip.distanceM,
+          tripDurationSec: trip.durationSec,
+        }
       )
       .accounts({
         globalState,
+        countryState: getCountryAddress(program, "SGP"),
         customerInfra: customerInfraData.publicKey,
         driverInfra: driverInfraAddress,
         job: jobPda,
+        driver: getDriverAddress(program, driverUuid),
+        driverProfile: getDriverProfileAddress(
+          program,
+          driverInfraAddress,
+          driverUuid
+        ),
         customerInfraOwner: customerInfraOwner.publicKey,
         customerInfraOwnerStable: customerInfraOwnerStableAta,
         jobEscrowStable: jobEscrowStableAta,
         mint: stableMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
       })
+      .preInstructions([quoteSignature])
       .signers([customerInfraOwner])
       .rpc();
+    if (expectedError) {
+      assert.fail("Request ride should fail");
+    }
   } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
     console.log("error:", error);
   }
 
@@ -415,15 +493,115 @@ export const customerRequestRide = async (
     driverInfraCount,
     encryptedData,
     totalFees,
+    rideRequirements,
     allAccounts
   );
 };
 
+export const customerReserveDriver = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  holdSec: anchor.BN,
+  expectedError?: string
+) => {
+  const { program, customerInfraOwner, driverInfraOwner } = allAccounts;
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+
+  try {
+    await program.methods
+      .customerReserveDriver(
+        "SGP",
+        customerInfraData.account.customerInfraCount,
+        driverInfraData.account.driverInfraCount,
+        driverUuid,
+        holdSec
+      )
+      .accounts({
+        countryState: getCountryAddress(program, "SGP"),
+        customerInfra: customerInfraData.publicKey,
+        driverInfra: driverInfraData.publicKey,
+        driver: getDriverAddress(program, driverUuid),
+        customerInfraOwner: customerInfraOwner.publicKey,
+      })
+      .signers([customerInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Reservation should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const driverData = await getDriverData(program, driverUuid);
+  expect(driverData.reservedBy?.toString()).to.equal(
+    customerInfraData.publicKey.toString(),
+    "reservedBy"
+  );
+  expect(+driverData.reservedUntil).to.be.greaterThan(
+    Math.floor(Date.now() / 1000) - 60,
+    "reservedUntil"
+  );
+};
+
+export const customerReleaseDriver = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  expectedError?: string
+) => {
+  const { program, customerInfraOwner } = allAccounts;
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+
+  try {
+    await program.methods
+      .customerReleaseDriver(
+        "SGP",
+        customerInfraData.account.customerInfraCount,
+        driverUuid
+      )
+      .accounts({
+        customerInfra: customerInfraData.publicKey,
+        driver: getDriverAddress(program, driverUuid),
+        customerInfraOwner: customerInfraOwner.publicKey,
+      })
+      .signers([customerInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Release should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const driverData = await getDriverData(program, driverUuid);
+  expect(driverData.reservedBy).to.equal(null, "reservedBy");
+};
+
 // Customer application has all required infomation
 export const driverAcceptedJob = async (
   allAccounts: IAllAccounts,
   driverInfraOwner: PublicKey,
-  driverUuid: string
+  driverUuid: string,
+  expectedError?: string
 ) => {
   const { program, customerInfraOwner } = allAccounts;
   const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
@@ -441,7 +619,8 @@ export const driverAcceptedJob = async (
     driverUuid
   );
 
-  const distination = { lat: 1.28272, long: 103.845455 };
+  // Micro-degrees
+  const distination = { lat: 1_282_720, long: 103_845_455 };
 
   try {
     await program.methods
@@ -462,19 +641,46 @@ export const driverAcceptedJob = async (
       })
       .signers([customerInfraOwner])
       .rpc();
-    assert.ok(true);
+    if (expectedError) {
+      assert.fail("Accept should fail");
+    }
   } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
     console.log("error", error);
     assert.fail();
   }
+
+  // ASSERT TEST
+  const driverInfraAfter = await getDriverInfraDataByAddress(
+    program,
+    driverInfraData.publicKey
+  );
+  const customerInfraAfter = await program.account.customerInfra.fetch(
+    customerInfraData.publicKey
+  );
+  expect(+driverInfraAfter.matchedRide).to.equal(
+    +driverInfraData.account.matchedRide + 1,
+    "driver matchedRide"
+  );
+  expect(+customerInfraAfter.matchedRide).to.equal(
+    +customerInfraData.account.matchedRide + 1,
+    "customer matchedRide"
+  );
 };
 export const customerCancelJob = async (
   allAccounts: IAllAccounts,
   driverUuid: string,
-  shouldPass = true
+  shouldPass = true,
+  driverPaysCent?: anchor.BN,
+  otherDriverUuid?: string
 ) => {
   const { program, customerInfraOwner, stableMint, driverInfraOwner } =
     allAccounts;
+  // Driver account passed in, another driver than the job's when set
+  const driverAccountUuid = otherDriverUuid ?? driverUuid;
   const countryPda = getCountryAddress(program, "SGP");
   const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
     program,
@@ -484,7 +690,7 @@ export const customerCancelJob = async (
     program,
     driverInfraOwner.publicKey
   );
-  const driverAddress = getDriverAddress(program, driverUuid);
+  const driverAddress = getDriverAddress(program, driverAccountUuid);
   const jobData = await getJobData(
     program,
     customerInfraData.publicKey,
@@ -501,12 +707,30 @@ export const customerCancelJob = async (
     driverInfraData.publicKey,
     true
   );
+  const jobEscrowStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    jobData.publicKey,
+    true
+  );
+  const driverBefore = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  const customerBefore = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
+  const escrowBefore = await getAccount(
+    program.provider.connection,
+    jobEscrowStableAta
+  );
 
+  let signature: string;
   try {
-    await program.methods
+    signature = await program.methods
       .customerCancelRide(
         "SGP",
-        driverUuid,
+        driverAccountUuid,
         customerInfraData.account.customerInfraCount,
         driverInfraData.account.driverInfraCount,
         jobData.account.jobCount
@@ -518,6 +742,7 @@ export const customerCancelJob = async (
         driver: driverAddress,
         job: jobData.publicKey,
         customerInfraOwner: customerInfraOwner.publicKey,
+        jobEsrowStable: jobEscrowStableAta,
         customerInfraStable: customerInfraStableAta,
         driverInfraStable: driverInfraStableAta,
         mint: stableMint,
@@ -536,10 +761,130 @@ export const customerCancelJob = async (
       assert.fail();
     }
   } catch (error) {
+    if (otherDriverUuid) {
+      expect(error.message).to.include("Error Code: ConstraintRaw");
+      return;
+    }
     if (shouldPass) {
       expect(error.message).to.include("data is undefined");
     } else {
       expect(error.message).to.include("assert.fail()");
     }
   }
+
+  if (!shouldPass) {
+    return;
+  }
+  // Escrowed fare is refunded and the escrow closed
+  const escrowInfo = await program.provider.connection.getAccountInfo(
+    jobEscrowStableAta
+  );
+  assert.equal(escrowInfo, null, "escrow closed");
+  // Tier applied is recorded in the cancellation event
+  const [cancelled] = await getEvents(program, signature, "JobCancelled");
+  expect(cancelled.job.toString()).to.equal(
+    jobData.publicKey.toString(),
+    "cancelled job"
+  );
+  expect(+cancelled.feeCent).to.equal(+(driverPaysCent ?? 0), "feeCent");
+  if (driverPaysCent) {
+    expect(Object.keys(cancelled.tier.stage)[0]).to.equal(
+      "etaBreach",
+      "tier stage"
+    );
+  }
+  const customerAfter = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
+  // Driver infra compensates the customer infra for a missed pickup ETA
+  const compensation = driverPaysCent ?? new anchor.BN(0);
+  expect(Number(customerAfter.amount - customerBefore.amount)).to.equal(
+    Number(escrowBefore.amount) + +compensation,
+    "customer infra refunded"
+  );
+  if (driverPaysCent) {
+    const driverAfter = await getAccount(
+      program.provider.connection,
+      driverInfraStableAta
+    );
+    expect(Number(driverBefore.amount - driverAfter.amount)).to.equal(
+      +driverPaysCent,
+      "driver infra paid"
+    );
+  }
+};
+
+export const customerRateDriver = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  rating: number,
+  expectedError?: string
+) => {
+  const { program, customerInfraOwner, driverInfraOwner } = allAccounts;
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+  const driverProfileAddress = getDriverProfileAddress(
+    program,
+    driverInfraData.publicKey,
+    driverUuid
+  );
+  const profileBefore = await program.account.driverProfile.fetch(
+    driverProfileAddress
+  );
+
+  try {
+    await program.methods
+      .customerRateDriver(
+        "SGP",
+        customerInfraData.account.customerInfraCount,
+        driverInfraData.account.driverInfraCount,
+        jobData.account.jobCount,
+        rating
+      )
+      .accounts({
+        customerInfra: customerInfraData.publicKey,
+        driverInfra: driverInfraData.publicKey,
+        job: jobData.publicKey,
+        driverProfile: driverProfileAddress,
+        customerInfraOwner: customerInfraOwner.publicKey,
+      })
+      .signers([customerInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Rating should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const profileAfter = await program.account.driverProfile.fetch(
+    driverProfileAddress
+  );
+  expect(+profileAfter.ratingSum).to.equal(
+    +profileBefore.ratingSum + rating,
+    "ratingSum"
+  );
+  expect(+profileAfter.ratingCount).to.equal(
+    +profileBefore.ratingCount + 1,
+    "ratingCount"
+  );
+  const jobAfter = await program.account.job.fetch(jobData.publicKey);
+  expect(jobAfter.driverRating).to.equal(rating, "driverRating");
 };
@@ -0,0 +1,399 @@
+import { getAccount, getAssociatedTokenAddress } from "@solana/spl-token";
+import { assert, expect } from "chai";
+import { createHash } from "crypto";
+import {
+  getCountryAddress,
+  getCustomerInfraDataByUpdateAuthority,
+  getDisputeAddress,
+  getDriverAddress,
+  getDriverInfraDataByUpdateAuthority,
+  getGlobalAddress,
+  getJobData,
+} from "../utils/pda";
+import { IAllAccounts } from "../utils/types";
+
+const evidenceHash = (evidence: string) =>
+  Array.from(createHash("sha256").update(evidence).digest());
+
+const getDisputeParties = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string
+) => {
+  const { program, driverInfraOwner, customerInfraOwner } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+  return { driverInfraData, customerInfraData, jobData };
+};
+
+// Country and global treasuries take their platform share out of a disputed escrow
+const getPlatformStableAccounts = async (allAccounts: IAllAccounts) => {
+  const { program, stableMint } = allAccounts;
+  const globalAddress = getGlobalAddress(program);
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    getCountryAddress(program),
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalAddress,
+    true
+  );
+  return { globalAddress, countryStableAta, globalStableAta };
+};
+
+export const customerRaiseIssue = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  expectedError?: string
+) => {
+  const { program, customerInfraOwner } = allAccounts;
+  const { driverInfraData, customerInfraData, jobData } =
+    await getDisputeParties(allAccounts, driverUuid);
+  const disputeAddress = getDisputeAddress(program, jobData.publicKey);
+
+  try {
+    await program.methods
+      .customerRaiseIssue(
+        "SGP",
+        customerInfraData.account.customerInfraCount,
+        driverInfraData.account.driverInfraCount,
+        jobData.account.jobCount,
+        { overcharge: {} },
+        evidenceHash("customer receipt")
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        customerInfra: customerInfraData.publicKey,
+        customerInfraOwner: customerInfraOwner.publicKey,
+        driverInfra: driverInfraData.publicKey,
+        job: jobData.publicKey,
+        dispute: disputeAddress,
+        driver: getDriverAddress(program, driverUuid),
+      })
+      .signers([customerInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Raise issue should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const dispute = await program.account.dispute.fetch(disputeAddress);
+  assert.equal(dispute.job.toString(), jobData.publicKey.toString(), "job");
+  assert.deepEqual(dispute.opener, { customerInfra: {} }, "opener");
+  assert.deepEqual(dispute.reason, { overcharge: {} }, "reason");
+  assert.equal(dispute.customerEvidence.length, 1, "customerEvidence");
+  assert.equal(dispute.responseDeadline.gt(dispute.openedTime), true);
+  const job = await getJobData(program, customerInfraData.publicKey, driverUuid);
+  assert.deepEqual(job.account.status, { disputeByCustomer: {} }, "status");
+};
+
+export const driverSubmitDisputeEvidence = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  shouldPass = true
+) => {
+  const { program, driverInfraOwner, bozo } = allAccounts;
+  const { driverInfraData, customerInfraData, jobData } =
+    await getDisputeParties(allAccounts, driverUuid);
+  const disputeAddress = getDisputeAddress(program, jobData.publicKey);
+  const authority = shouldPass ? driverInfraOwner : bozo;
+
+  try {
+    await program.methods
+      .submitDisputeEvidence(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount,
+        evidenceHash("driver trip log")
+      )
+      .accounts({
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        job: jobData.publicKey,
+        dispute: disputeAddress,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Submit should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("UnauthorizedDisputeParty");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const dispute = await program.account.dispute.fetch(disputeAddress);
+  assert.equal(dispute.driverEvidence.length, 1, "driverEvidence");
+  assert.notEqual(dispute.respondedTime, null, "respondedTime");
+};
+
+export const resolveDispute = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  driverAwardBasisPoint: number,
+  shouldPass = true
+) => {
+  const { program, mainWallet1, mainWallet2, stableMint } = allAccounts;
+  const { driverInfraData, customerInfraData, jobData } =
+    await getDisputeParties(allAccounts, driverUuid);
+  const disputeAddress = getDisputeAddress(program, jobData.publicKey);
+  const countryAuthority = shouldPass ? mainWallet1 : mainWallet2;
+
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  const customerInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    customerInfraData.publicKey,
+    true
+  );
+  const jobEscrowStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    jobData.publicKey,
+    true
+  );
+  const { globalAddress, countryStableAta, globalStableAta } =
+    await getPlatformStableAccounts(allAccounts);
+  const connection = program.provider.connection;
+  const escrowBefore = await getAccount(connection, jobEscrowStableAta);
+  const driverBefore = await getAccount(connection, driverInfraStableAta);
+  const countryBefore = await getAccount(connection, countryStableAta);
+  const globalBefore = await getAccount(connection, globalStableAta);
+
+  try {
+    await program.methods
+      .resolveDispute(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount,
+        driverAwardBasisPoint
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        countryAuthority: countryAuthority.publicKey,
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        job: jobData.publicKey,
+        dispute: disputeAddress,
+        jobEsrowStable: jobEscrowStableAta,
+        driverInfraStable: driverInfraStableAta,
+        customerInfraStable: customerInfraStableAta,
+        countryStableAccount: countryStableAta,
+        globalState: globalAddress,
+        globalStableAccount: globalStableAta,
+        mint: stableMint,
+      })
+      .signers([countryAuthority])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Resolve should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("Error Code: ConstraintRaw");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const dispute = await program.account.dispute.fetch(disputeAddress);
+  assert.equal(dispute.driverAwardBasisPoint, driverAwardBasisPoint);
+  assert.notEqual(dispute.resolvedTime, null, "resolvedTime");
+  const escrowInfo = await connection.getAccountInfo(jobEscrowStableAta);
+  assert.equal(escrowInfo, null, "escrow should be closed");
+  // Platform shares come off the top, the parties split the rest
+  const escrowAmount = Number(escrowBefore.amount);
+  const platformBasisPoint = jobData.account.distribution
+    .filter(
+      (share) =>
+        !share.provider.equals(driverInfraData.publicKey) &&
+        !share.provider.equals(customerInfraData.publicKey)
+    )
+    .reduce((total, share) => total + share.basisPointPayout, 0);
+  const platformFee = Math.floor((escrowAmount * platformBasisPoint) / 10_000);
+  const countryAfter = await getAccount(connection, countryStableAta);
+  const globalAfter = await getAccount(connection, globalStableAta);
+  expect(
+    Number(
+      countryAfter.amount -
+        countryBefore.amount +
+        (globalAfter.amount - globalBefore.amount)
+    )
+  ).to.equal(platformFee, "platformFee");
+  const driverAfter = await getAccount(connection, driverInfraStableAta);
+  expect(Number(driverAfter.amount - driverBefore.amount)).to.equal(
+    Math.floor(((escrowAmount - platformFee) * driverAwardBasisPoint) / 10_000),
+    "driverAward"
+  );
+
+  const driverInfraAfter = await getDriverInfraDataByUpdateAuthority(
+    program,
+    allAccounts.driverInfraOwner.publicKey
+  );
+  const customerInfraAfter = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    allAccounts.customerInfraOwner.publicKey
+  );
+  assert.equal(
+    +driverInfraAfter.account.disputeCases,
+    +driverInfraData.account.disputeCases + 1,
+    "driver disputeCases"
+  );
+  assert.equal(
+    +customerInfraAfter.account.disputeCases,
+    +customerInfraData.account.disputeCases + 1,
+    "customer disputeCases"
+  );
+  if (driverAwardBasisPoint * 2 < 10_000) {
+    assert.equal(
+      +driverInfraAfter.account.casesLostInDispute,
+      +driverInfraData.account.casesLostInDispute + 1,
+      "driver casesLostInDispute"
+    );
+  }
+};
+
+export const driverRaiseIssue = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const { driverInfraData, customerInfraData, jobData } =
+    await getDisputeParties(allAccounts, driverUuid);
+  const disputeAddress = getDisputeAddress(program, jobData.publicKey);
+
+  await program.methods
+    .driverRaiseIssue(
+      "SGP",
+      driverInfraData.account.driverInfraCount,
+      jobData.account.jobCount,
+      { misconduct: {} },
+      evidenceHash("driver dashcam")
+    )
+    .accounts({
+      countryState: getCountryAddress(program),
+      driverInfra: driverInfraData.publicKey,
+      driverInfraOwner: driverInfraOwner.publicKey,
+      job: jobData.publicKey,
+      dispute: disputeAddress,
+      driver: getDriverAddress(program, driverUuid),
+    })
+    .signers([driverInfraOwner])
+    .rpc();
+
+  // ASSERT TEST
+  const dispute = await program.account.dispute.fetch(disputeAddress);
+  assert.deepEqual(dispute.opener, { driverInfra: {} }, "opener");
+  assert.equal(dispute.driverEvidence.length, 1, "driverEvidence");
+  const job = await getJobData(program, customerInfraData.publicKey, driverUuid);
+  assert.deepEqual(job.account.status, { disputeByDriver: {} }, "status");
+};
+
+export const claimDefaultJudgment = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  shouldPass = true
+) => {
+  const { program, bozo: caller, stableMint } = allAccounts;
+  const { driverInfraData, customerInfraData, jobData } =
+    await getDisputeParties(allAccounts, driverUuid);
+  const disputeAddress = getDisputeAddress(program, jobData.publicKey);
+
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  const customerInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    customerInfraData.publicKey,
+    true
+  );
+  const jobEscrowStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    jobData.publicKey,
+    true
+  );
+  const { globalAddress, countryStableAta, globalStableAta } =
+    await getPlatformStableAccounts(allAccounts);
+
+  try {
+    await program.methods
+      .claimDefaultJudgment(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        job: jobData.publicKey,
+        dispute: disputeAddress,
+        jobEsrowStable: jobEscrowStableAta,
+        driverInfraStable: driverInfraStableAta,
+        customerInfraStable: customerInfraStableAta,
+        countryStableAccount: countryStableAta,
+        globalState: globalAddress,
+        globalStableAccount: globalStableAta,
+        mint: stableMint,
+        caller: caller.publicKey,
+      })
+      .signers([caller])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Default judgment should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("DisputeResponseWindowNotOver");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const dispute = await program.account.dispute.fetch(disputeAddress);
+  assert.notEqual(dispute.resolvedTime, null, "resolvedTime");
+  const jobInfo = await program.provider.connection.getAccountInfo(
+    jobData.publicKey
+  );
+  assert.equal(jobInfo, null, "job should be closed");
+  const escrowInfo = await program.provider.connection.getAccountInfo(
+    jobEscrowStableAta
+  );
+  assert.equal(escrowInfo, null, "escrow should be closed");
+};
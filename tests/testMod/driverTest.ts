@@ -1,8 +1,10 @@
 import * as anchor from "@project-serum/anchor";
-import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { getAccount, getAssociatedTokenAddress } from "@solana/spl-token";
 import { assert, expect } from "chai";
 import {
+  assertDriverArrived,
   assertDriverCompleteJob,
+  assertDriverPickupCustomer,
   createDriverAssert,
   initDriverInfraAssert,
   updateDriverInfraCompanyAssert,
@@ -17,12 +19,17 @@ import {
   getCountryAddress,
   getCountryData,
   getVehicleAddress,
+  getServiceAddress,
+  getPassengerTypeAddress,
+  getDriverProfileAddress,
   getDriverInfraDataByUpdateAuthority,
   getCustomerInfraDataByUpdateAuthority,
   getJobData,
   getCustomerInfraAddress,
+  getGlobalAddress,
+  getDriverInfraDataByAddress,
 } from "../utils/pda";
-import { IAllAccounts } from "../utils/types";
+import { DriverAvailability, IAllAccounts } from "../utils/types";
 
 export const initDriverInfra = async (
   allAccounts: IAllAccounts,
@@ -261,15 +268,107 @@ export const updateDriverInfraBasisPointTestWrongAuth = async (
   }
 };
 
+const ONE_YEAR_SEC = 365 * 24 * 60 * 60;
+
+export const initDriverProfile = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  licenseExpiry = Math.floor(Date.now() / 1000) + ONE_YEAR_SEC,
+  credentialExpiry = Math.floor(Date.now() / 1000) + ONE_YEAR_SEC
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const driverProfileAddress = getDriverProfileAddress(
+    program,
+    driverInfraData.publicKey,
+    driverUuid
+  );
+
+  await program.methods
+    .initDriverProfile(
+      "SGP",
+      driverInfraData.account.driverInfraCount,
+      driverUuid,
+      new anchor.BN(licenseExpiry),
+      new anchor.BN(credentialExpiry)
+    )
+    .accounts({
+      driverInfra: driverInfraData.publicKey,
+      driverProfile: driverProfileAddress,
+      driverInfraOwner: driverInfraOwner.publicKey,
+    })
+    .signers([driverInfraOwner])
+    .rpc();
+
+  // ASSERT TEST
+  const data = await program.account.driverProfile.fetch(driverProfileAddress);
+  assert.equal(data.driverUuid, driverUuid, "driverUuid");
+  assert.equal(+data.licenseExpiry, licenseExpiry, "licenseExpiry");
+  assert.equal(+data.credentialExpiry, credentialExpiry, "credentialExpiry");
+  assert.equal(+data.completedJob, 0, "completedJob");
+};
+
+export const updateDriverProfileCredential = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  licenseExpiry = Math.floor(Date.now() / 1000) + ONE_YEAR_SEC,
+  credentialExpiry = Math.floor(Date.now() / 1000) + ONE_YEAR_SEC
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const driverProfileAddress = getDriverProfileAddress(
+    program,
+    driverInfraData.publicKey,
+    driverUuid
+  );
+
+  await program.methods
+    .updateDriverProfileCredential(
+      "SGP",
+      driverInfraData.account.driverInfraCount,
+      driverUuid,
+      new anchor.BN(licenseExpiry),
+      new anchor.BN(credentialExpiry)
+    )
+    .accounts({
+      driverInfra: driverInfraData.publicKey,
+      driverProfile: driverProfileAddress,
+      driverInfraOwner: driverInfraOwner.publicKey,
+    })
+    .signers([driverInfraOwner])
+    .rpc();
+
+  // ASSERT TEST
+  const data = await program.account.driverProfile.fetch(driverProfileAddress);
+  assert.equal(+data.licenseExpiry, licenseExpiry, "licenseExpiry");
+  assert.equal(+data.credentialExpiry, credentialExpiry, "credentialExpiry");
+};
+
 export const createDriver = async (
   allAccounts: IAllAccounts,
   driverUuid: string,
-  publicKeyPem: string | Buffer
+  publicKeyPem: string | Buffer,
+  expectedError?: string,
+  services = [new anchor.BN(0)],
+  passengerTypes = [new anchor.BN(0)]
 ) => {
   const { program, driverInfraOwner } = allAccounts;
   const countryPda = getCountryAddress(program, "SGP");
   const driverAddress = getDriverAddress(program, driverUuid);
   const vehicleAddress = getVehicleAddress(program, new anchor.BN(0));
+  // Catalog accounts in the same order as the service and passenger type IDs
+  const catalogAccounts = [
+    ...services.map((serviceCount) => getServiceAddress(program, serviceCount)),
+    ...passengerTypes.map((passengerTypeCount) =>
+      getPassengerTypeAddress(program, passengerTypeCount)
+    ),
+  ].map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }));
   const driverInfraData = await getDriverInfraDataByUpdateAuthority(
     program,
     driverInfraOwner.publicKey
@@ -278,7 +377,20 @@ export const createDriver = async (
     program,
     driverInfraData.account.driverInfraCount
   );
-  const coordinates = { lat: 1.28272, long: 103.845455 };
+  const driverProfileAddress = getDriverProfileAddress(
+    program,
+    driverInfraPda,
+    driverUuid
+  );
+  // Every driver needs a profile before going online
+  const driverProfileInfo = await program.provider.connection.getAccountInfo(
+    driverProfileAddress
+  );
+  if (!driverProfileInfo) {
+    await initDriverProfile(allAccounts, driverUuid);
+  }
+  // Micro-degrees
+  const coordinates = { lat: 1_282_720, long: 103_845_455 };
 
   try {
     await program.methods
@@ -287,22 +399,31 @@ export const createDriver = async (
         driverInfraData.account.driverInfraCount,
         driverUuid,
         publicKeyPem.toString(),
-        [new anchor.BN(0)],
-        [new anchor.BN(0)],
+        services,
+        passengerTypes,
         new anchor.BN(0),
         coordinates
       )
       .accounts({
         countryState: countryPda,
         driverInfra: driverInfraPda,
+        driverProfile: driverProfileAddress,
         driver: driverAddress,
         vehicle: vehicleAddress,
         driverInfraAuthority: driverInfraOwner.publicKey,
         locationUpdateAuthority: driverInfraOwner.publicKey,
       })
+      .remainingAccounts(catalogAccounts)
       .signers([driverInfraOwner])
       .rpc();
+    if (expectedError) {
+      assert.fail("Start work should fail");
+    }
   } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
     console.log("error:", error);
     assert.fail();
   }
@@ -344,11 +465,12 @@ export const createDriver = async (
 
 export const updateDriverLocation = async (
   allAccounts: IAllAccounts,
-  uuid: string
+  uuid: string,
+  coordinates = { lat: 1_333_330, long: 102_845_885 }, // Micro-degrees
+  expectedError?: string
 ) => {
   const { program, driverInfraOwner } = allAccounts;
   const driverAddress = getDriverAddress(program, uuid);
-  const coordinates = { lat: 1.33333, long: 102.845885 };
 
   const driverDataBefore = await getDriverData(program, uuid);
 
@@ -361,7 +483,14 @@ export const updateDriverLocation = async (
       })
       .signers([driverInfraOwner])
       .rpc();
+    if (expectedError) {
+      assert.fail("Location update should fail");
+    }
   } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
     console.log("error:", error);
     assert.fail();
   }
@@ -415,6 +544,104 @@ export const updateDriverLocation = async (
 //   );
 // };
 
+export const driverSetAvailability = async (
+  allAccounts: IAllAccounts,
+  uuid: string,
+  availability: DriverAvailability,
+  expectedError?: string
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+
+  try {
+    await program.methods
+      .driverSetAvailability(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        uuid,
+        availability
+      )
+      .accounts({
+        driverInfra: driverInfraData.publicKey,
+        driver: getDriverAddress(program, uuid),
+        authority: driverInfraOwner.publicKey,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Set availability should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    console.log("error:", error);
+    assert.fail();
+  }
+
+  // ASSERT TEST
+  const driverData = await getDriverData(program, uuid);
+  expect(driverData.availability).to.deep.equal(availability, "availability");
+};
+
+// Permissionless crank, bozo pays the fee
+export const markStaleDriver = async (
+  allAccounts: IAllAccounts,
+  uuid: string,
+  expectedError?: string
+) => {
+  const { program, driverInfraOwner, bozo } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const driverAddress = getDriverAddress(program, uuid);
+  const driverBefore = await getDriverData(program, uuid);
+
+  try {
+    await program.methods
+      .markStaleDriver("SGP", driverInfraData.account.driverInfraCount, uuid)
+      .accounts({
+        countryState: getCountryAddress(program, "SGP"),
+        driverInfra: driverInfraData.publicKey,
+        driver: driverAddress,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        caller: bozo.publicKey,
+      })
+      .signers([bozo])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Mark stale should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    console.log("error:", error);
+    assert.fail();
+  }
+
+  // ASSERT TEST
+  // Idle sessions are closed, drivers still holding a job only go offline
+  const driverInfo = await program.provider.connection.getAccountInfo(
+    driverAddress
+  );
+  if (driverBefore.activeJob) {
+    const driverData = await getDriverData(program, uuid);
+    expect(driverData.availability).to.deep.equal(
+      { offline: {} },
+      "availability"
+    );
+  } else {
+    expect(driverInfo).to.equal(null, "driver closed");
+  }
+};
+
 export const driverCompleteJob = async (
   allAccounts: IAllAccounts,
   driverUuid: string
@@ -461,6 +688,21 @@ export const driverCompleteJob = async (
     jobData.publicKey,
     true
   );
+  const globalAddress = getGlobalAddress(program);
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    countryAddress,
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalAddress,
+    true
+  );
+  const customerBefore = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
 
   try {
     await program.methods
@@ -476,11 +718,19 @@ export const driverCompleteJob = async (
         driverInfra: driverInfraPda,
         customerInfra: customerInfraPda,
         driver: driverAddress,
+        driverProfile: getDriverProfileAddress(
+          program,
+          driverInfraPda,
+          driverUuid
+        ),
         job: jobData.publicKey,
         driverInfraOwner: driverInfraOwner.publicKey,
         jobEsrowStable: jobEscrowStableAta,
         driverInfraStable: driverInfraStableAta,
         customerInfraStable: customerInfraStableAta,
+        countryStableAccount: countryStableAta,
+        globalState: globalAddress,
+        globalStableAccount: globalStableAta,
         mint: stableMint,
       })
       .signers([driverInfraOwner])
@@ -495,6 +745,28 @@ export const driverCompleteJob = async (
     driverUuid,
     customerInfraData.publicKey
   );
+  // Escrow holds the final fare, the rest of the quote is settled with the customer infra
+  const jobAfter = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+  const escrowAfter = await getAccount(
+    program.provider.connection,
+    jobEscrowStableAta
+  );
+  expect(Number(escrowAfter.amount)).to.equal(
+    +jobAfter.account.totalFeeCent,
+    "escrowed fare"
+  );
+  const customerAfter = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
+  expect(Number(customerAfter.amount - customerBefore.amount)).to.equal(
+    +jobData.account.totalFeeCent - +jobAfter.account.totalFeeCent,
+    "customer infra fare adjustment"
+  );
 };
 
 export const driverCompleteJobAfterFinialize = async (
@@ -543,6 +815,17 @@ export const driverCompleteJobAfterFinialize = async (
     jobData.publicKey,
     true
   );
+  const globalAddress = getGlobalAddress(program);
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    countryAddress,
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalAddress,
+    true
+  );
 
   try {
     await program.methods
@@ -558,11 +841,19 @@ export const driverCompleteJobAfterFinialize = async (
         driverInfra: driverInfraPda,
         customerInfra: customerInfraPda,
         driver: driverAddress,
+        driverProfile: getDriverProfileAddress(
+          program,
+          driverInfraPda,
+          driverUuid
+        ),
         job: jobData.publicKey,
         driverInfraOwner: driverInfraOwner.publicKey,
         jobEsrowStable: jobEscrowStableAta,
         driverInfraStable: driverInfraStableAta,
         customerInfraStable: customerInfraStableAta,
+        countryStableAccount: countryStableAta,
+        globalState: globalAddress,
+        globalStableAccount: globalStableAta,
         mint: stableMint,
       })
       .signers([driverInfraOwner])
@@ -572,6 +863,14 @@ export const driverCompleteJobAfterFinialize = async (
   }
   const data = await getJobData(program, customerInfraPda, driverUuid);
   assert.equal(!!data, false, `Account should be closed by got ${data}`);
+
+  // Driver session stays open and the profile keeps the history
+  const driverData = await getDriverData(program, driverUuid);
+  assert.equal(driverData.nextLocation, null, "nextLocation");
+  const driverProfileData = await program.account.driverProfile.fetch(
+    driverData.profile
+  );
+  assert.equal(+driverProfileData.completedJob, 1, "completedJob");
 };
 
 // export const endDriverWorkWrongAuth = async (
@@ -645,12 +944,15 @@ export const driverCompleteJobAfterFinialize = async (
 export const driverCancelJob = async (
   allAccounts: IAllAccounts,
   driverUuid: string,
-  shouldPass = true
+  shouldPass = true,
+  otherDriverUuid?: string
 ) => {
   const { program, driverInfraOwner, customerInfraOwner, stableMint } =
     allAccounts;
+  // Driver accounts passed in, another driver than the job's when set
+  const driverAccountUuid = otherDriverUuid ?? driverUuid;
   const countryAddress = getCountryAddress(program);
-  const driverAddress = getDriverAddress(program, driverUuid);
+  const driverAddress = getDriverAddress(program, driverAccountUuid);
   const driverInfraData = await getDriverInfraDataByUpdateAuthority(
     program,
     driverInfraOwner.publicKey
@@ -694,7 +996,7 @@ export const driverCancelJob = async (
     await program.methods
       .driverCancelJob(
         "SGP",
-        driverUuid,
+        driverAccountUuid,
         driverInfraData.account.driverInfraCount,
         customerInfraData.account.customerInfraCount,
         jobData.account.jobCount
@@ -704,6 +1006,11 @@ export const driverCancelJob = async (
         driverInfra: driverInfraPda,
         customerInfra: customerInfraPda,
         driver: driverAddress,
+        driverProfile: getDriverProfileAddress(
+          program,
+          driverInfraPda,
+          driverAccountUuid
+        ),
         job: jobData.publicKey,
         driverInfraOwner: driverInfraOwner.publicKey,
         jobEsrowStable: jobEscrowStableAta,
@@ -724,6 +1031,10 @@ export const driverCancelJob = async (
       assert.fail();
     }
   } catch (error) {
+    if (otherDriverUuid) {
+      expect(error.message).to.include("Error Code: ConstraintRaw");
+      return;
+    }
     if (shouldPass) {
       expect(error.message).to.include("data is undefined");
       // CHECK FEES WAS NOT DEDUCTED
@@ -731,4 +1042,428 @@ export const driverCancelJob = async (
       expect(error.message).to.include("assert.fail()");
     }
   }
+  if (shouldPass) {
+    const escrowInfo = await program.provider.connection.getAccountInfo(
+      jobEscrowStableAta
+    );
+    assert.equal(escrowInfo, null, "escrow closed");
+  }
+};
+
+export const driverReportNoShow = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  expectedError?: string
+) => {
+  const { program, driverInfraOwner, customerInfraOwner, stableMint } =
+    allAccounts;
+  const countryData = await getCountryData(program);
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  const customerInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    customerInfraData.publicKey,
+    true
+  );
+  const jobEscrowStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    jobData.publicKey,
+    true
+  );
+  const driverBefore = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  const customerBefore = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
+
+  try {
+    await program.methods
+      .driverReportNoShow(
+        "SGP",
+        driverUuid,
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        driver: getDriverAddress(program, driverUuid),
+        job: jobData.publicKey,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        jobEsrowStable: jobEscrowStableAta,
+        driverInfraStable: driverInfraStableAta,
+        customerInfraStable: customerInfraStableAta,
+        mint: stableMint,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Report no-show should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    console.log("error:", error);
+    assert.fail();
+  }
+
+  // No-show fee to the driver infra, the rest back to the customer infra
+  const totalFee = +jobData.account.totalFeeCent;
+  const noShowFee = Math.min(+countryData.noShowFeeCent, totalFee);
+  const driverAfter = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  const customerAfter = await getAccount(
+    program.provider.connection,
+    customerInfraStableAta
+  );
+  expect(Number(driverAfter.amount - driverBefore.amount)).to.equal(
+    noShowFee,
+    "driver infra no-show fee"
+  );
+  expect(Number(customerAfter.amount - customerBefore.amount)).to.equal(
+    totalFee - noShowFee,
+    "customer infra refund"
+  );
+  const jobInfo = await program.provider.connection.getAccountInfo(
+    jobData.publicKey
+  );
+  expect(jobInfo).to.equal(null, "job closed");
+  const escrowInfo = await program.provider.connection.getAccountInfo(
+    jobEscrowStableAta
+  );
+  expect(escrowInfo).to.equal(null, "escrow closed");
+  const customerInfraAfter = await program.account.customerInfra.fetch(
+    customerInfraData.publicKey
+  );
+  expect(+customerInfraAfter.cancellation).to.equal(
+    +customerInfraData.account.cancellation + 1,
+    "cancellation"
+  );
+  const driverData = await getDriverData(program, driverUuid);
+  expect(driverData.activeJob).to.equal(null, "activeJob");
+};
+
+export const driverArrived = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  shouldPass = true,
+  expectedError = "IncorrectLifecycleJobAccepted"
+) => {
+  const { program, driverInfraOwner, customerInfraOwner } = allAccounts;
+  const driverAddress = getDriverAddress(program, driverUuid);
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+
+  try {
+    await program.methods
+      .driverArrived(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        driverUuid,
+        jobData.account.jobCount
+      )
+      .accounts({
+        driverInfra: driverInfraData.publicKey,
+        driver: driverAddress,
+        job: jobData.publicKey,
+        authority: driverInfraOwner.publicKey,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail();
+    }
+  } catch (error) {
+    if (shouldPass) {
+      console.log("error:", error);
+      throw error;
+    } else {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+  }
+
+  await assertDriverArrived(program, driverUuid, customerInfraData.publicKey);
+};
+
+export const driverPickupCustomer = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  expectedError?: string
+) => {
+  const { program, driverInfraOwner, customerInfraOwner, stableMint } =
+    allAccounts;
+  const countryAddress = getCountryAddress(program);
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  const customerInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    customerInfraData.publicKey,
+    true
+  );
+
+  try {
+    await program.methods
+      .driverPickupCustomer(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount
+      )
+      .accounts({
+        countryState: countryAddress,
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        job: jobData.publicKey,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        driverInfraStable: driverInfraStableAta,
+        customerInfraStable: customerInfraStableAta,
+        mint: stableMint,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Pickup should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    console.log("error:", error);
+    assert.fail();
+  }
+
+  await assertDriverPickupCustomer(
+    program,
+    driverUuid,
+    customerInfraData.publicKey
+  );
+};
+
+const getDriverInfraDepositAccounts = async (allAccounts: IAllAccounts) => {
+  const { program, stableMint, driverInfraOwner } = allAccounts;
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const driverInfraOwnerStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraOwner.publicKey,
+    true
+  );
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  return { driverInfraData, driverInfraOwnerStableAta, driverInfraStableAta };
+};
+
+export const getDriverInfraStableBalance = async (
+  allAccounts: IAllAccounts
+) => {
+  const { driverInfraStableAta } = await getDriverInfraDepositAccounts(
+    allAccounts
+  );
+  const stable = await getAccount(
+    allAccounts.program.provider.connection,
+    driverInfraStableAta
+  );
+  return new anchor.BN(stable.amount.toString());
+};
+
+export const topUpDriverInfraDeposit = async (
+  allAccounts: IAllAccounts,
+  amount: anchor.BN
+) => {
+  const { program, stableMint, driverInfraOwner } = allAccounts;
+  const { driverInfraData, driverInfraOwnerStableAta, driverInfraStableAta } =
+    await getDriverInfraDepositAccounts(allAccounts);
+  const driverBefore = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+
+  await program.methods
+    .topUpDriverInfraDeposit(
+      "SGP",
+      driverInfraData.account.driverInfraCount,
+      amount
+    )
+    .accounts({
+      countryState: getCountryAddress(program),
+      driverInfra: driverInfraData.publicKey,
+      driverInfraOwner: driverInfraOwner.publicKey,
+      driverInfraOwnerStable: driverInfraOwnerStableAta,
+      driverInfraStable: driverInfraStableAta,
+      mint: stableMint,
+    })
+    .signers([driverInfraOwner])
+    .rpc();
+
+  // ASSERT TEST
+  const driverAfter = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  expect(Number(driverAfter.amount - driverBefore.amount)).to.equal(
+    +amount,
+    "topped up"
+  );
+};
+
+export const requestDriverInfraWithdrawal = async (
+  allAccounts: IAllAccounts,
+  amount: anchor.BN,
+  expectedError?: string
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const { driverInfraData, driverInfraStableAta } =
+    await getDriverInfraDepositAccounts(allAccounts);
+
+  try {
+    await program.methods
+      .requestDriverInfraWithdrawal(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        amount
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        driverInfra: driverInfraData.publicKey,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        driverInfraStable: driverInfraStableAta,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (expectedError) {
+      assert.fail("Withdrawal request should fail");
+    }
+  } catch (error) {
+    if (expectedError) {
+      expect(error.message).to.include(expectedError);
+      return;
+    }
+    throw error;
+  }
+
+  // ASSERT TEST
+  const data = await getDriverInfraDataByAddress(
+    program,
+    driverInfraData.publicKey
+  );
+  expect(+data.pendingWithdrawalCent).to.equal(+amount, "pendingWithdrawal");
+  assert.notEqual(data.withdrawalAvailableTime, null);
+};
+
+export const completeDriverInfraWithdrawal = async (
+  allAccounts: IAllAccounts,
+  shouldPass = true
+) => {
+  const { program, stableMint, driverInfraOwner } = allAccounts;
+  const { driverInfraData, driverInfraOwnerStableAta, driverInfraStableAta } =
+    await getDriverInfraDepositAccounts(allAccounts);
+  const ownerBefore = await getAccount(
+    program.provider.connection,
+    driverInfraOwnerStableAta
+  );
+
+  try {
+    await program.methods
+      .completeDriverInfraWithdrawal(
+        "SGP",
+        driverInfraData.account.driverInfraCount
+      )
+      .accounts({
+        countryState: getCountryAddress(program),
+        driverInfra: driverInfraData.publicKey,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        driverInfraOwnerStable: driverInfraOwnerStableAta,
+        driverInfraStable: driverInfraStableAta,
+        mint: stableMint,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Withdrawal should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("UnbondingPeriodNotOver");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const ownerAfter = await getAccount(
+    program.provider.connection,
+    driverInfraOwnerStableAta
+  );
+  expect(Number(ownerAfter.amount - ownerBefore.amount)).to.equal(
+    +driverInfraData.account.pendingWithdrawalCent,
+    "withdrawn"
+  );
+  const data = await getDriverInfraDataByAddress(
+    program,
+    driverInfraData.publicKey
+  );
+  expect(+data.pendingWithdrawalCent).to.equal(0, "pendingWithdrawal");
+  assert.equal(data.withdrawalAvailableTime, null);
 };
@@ -1,6 +1,8 @@
+import * as anchor from "@project-serum/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  getAccount,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
 import {
@@ -9,6 +11,7 @@ import {
   getGlobalAddress,
   getGlobalData,
   getPassengerTypeAddress,
+  getPassengerTypeData,
 } from "../utils/pda";
 import { IAllAccounts } from "../utils/types";
 import { assert, expect } from "chai";
@@ -68,3 +71,69 @@ export const addPassengerType = async (
     }
   }
 };
+
+export const approvePassengerType = async (
+  allAccounts: IAllAccounts,
+  passengerTypeCount: anchor.BN
+) => {
+  const { program, mainWallet1, stableMint } = allAccounts;
+  const globalPda = getGlobalAddress(program);
+  const passengerTypePda = getPassengerTypeAddress(program, passengerTypeCount);
+  const passengerTypeStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    passengerTypePda,
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalPda,
+    true
+  );
+  const initializerStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    mainWallet1.publicKey
+  );
+  const escrowBefore = await getAccount(
+    program.provider.connection,
+    passengerTypeStableAta
+  );
+  const globalBefore = await getAccount(
+    program.provider.connection,
+    globalStableAta
+  );
+
+  await program.methods
+    .approvePassengerType(passengerTypeCount)
+    .accounts({
+      globalState: globalPda,
+      passengerType: passengerTypePda,
+      passengerTypeEscrowAccount: passengerTypeStableAta,
+      globalTokenAccount: globalStableAta,
+      initializerTokenAccount: initializerStableAta,
+      initializer: mainWallet1.publicKey,
+      globalAuthority: mainWallet1.publicKey,
+      mint: stableMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const passengerTypeData = await getPassengerTypeData(
+    program,
+    passengerTypeCount
+  );
+  assert.equal(passengerTypeData.isValid, true, "isValid");
+  const globalAfter = await getAccount(
+    program.provider.connection,
+    globalStableAta
+  );
+  expect(Number(globalAfter.amount - globalBefore.amount)).to.equal(
+    Number(escrowBefore.amount),
+    "global treasury"
+  );
+  const escrowAfter = await program.provider.connection.getAccountInfo(
+    passengerTypeStableAta
+  );
+  assert.equal(escrowAfter, null, "escrow closed");
+};
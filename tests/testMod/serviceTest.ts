@@ -1,6 +1,8 @@
+import * as anchor from "@project-serum/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  getAccount,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
 import {
@@ -11,6 +13,7 @@ import {
   getGlobalAddress,
   getGlobalData,
   getServiceAddress,
+  getServiceData,
 } from "../utils/pda";
 import { IAllAccounts } from "../utils/types";
 import { assert, expect } from "chai";
@@ -71,3 +74,81 @@ export const addService = async (
     }
   }
 };
+
+export const approveService = async (
+  allAccounts: IAllAccounts,
+  serviceCount: anchor.BN,
+  shouldPass = true
+) => {
+  const { program, mainWallet1, bozo, stableMint } = allAccounts;
+  const globalAuthority = shouldPass ? mainWallet1 : bozo;
+  const countryPda = getCountryAddress(program, "SGP");
+  const servicePda = getServiceAddress(program, serviceCount);
+  const serviceStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    servicePda,
+    true
+  );
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    countryPda,
+    true
+  );
+  const initializerStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    mainWallet1.publicKey
+  );
+  const escrowBefore = await getAccount(
+    program.provider.connection,
+    serviceStableAta
+  );
+  const countryBefore = await getAccount(
+    program.provider.connection,
+    countryStableAta
+  );
+
+  try {
+    await program.methods
+      .approveService("SGP", serviceCount)
+      .accounts({
+        globalState: getGlobalAddress(program),
+        countryState: countryPda,
+        serviceType: servicePda,
+        serviceTypeEscrowAccount: serviceStableAta,
+        countryTokenAccount: countryStableAta,
+        initializerTokenAccount: initializerStableAta,
+        initializer: mainWallet1.publicKey,
+        globalAuthority: globalAuthority.publicKey,
+        mint: stableMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([globalAuthority])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Approval should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("Error Code: ConstraintRaw");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const serviceData = await getServiceData(program, serviceCount);
+  assert.equal(serviceData.isValid, true, "isValid");
+  const countryAfter = await getAccount(
+    program.provider.connection,
+    countryStableAta
+  );
+  expect(Number(countryAfter.amount - countryBefore.amount)).to.equal(
+    Number(escrowBefore.amount),
+    "country treasury"
+  );
+  const escrowAfter = await program.provider.connection.getAccountInfo(
+    serviceStableAta
+  );
+  assert.equal(escrowAfter, null, "escrow closed");
+};
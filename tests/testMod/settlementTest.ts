@@ -0,0 +1,134 @@
+import { getAccount, getAssociatedTokenAddress } from "@solana/spl-token";
+import { assert, expect } from "chai";
+import {
+  getCountryAddress,
+  getCountryData,
+  getCustomerInfraDataByUpdateAuthority,
+  getDriverInfraDataByUpdateAuthority,
+  getGlobalAddress,
+  getJobData,
+} from "../utils/pda";
+import { IAllAccounts } from "../utils/types";
+
+export const settleJob = async (
+  allAccounts: IAllAccounts,
+  driverUuid: string,
+  shouldPass = true
+) => {
+  const {
+    program,
+    driverInfraOwner,
+    customerInfraOwner,
+    mainWallet1: keeper,
+    stableMint,
+  } = allAccounts;
+  const countryAddress = getCountryAddress(program);
+  const countryData = await getCountryData(program);
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const customerInfraData = await getCustomerInfraDataByUpdateAuthority(
+    program,
+    customerInfraOwner.publicKey
+  );
+  const jobData = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  const customerInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    customerInfraData.publicKey,
+    true
+  );
+  const jobEscrowStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    jobData.publicKey,
+    true
+  );
+  const globalAddress = getGlobalAddress(program);
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    countryAddress,
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalAddress,
+    true
+  );
+  const keeperStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    keeper.publicKey,
+    true
+  );
+  const keeperBefore = await getAccount(
+    program.provider.connection,
+    keeperStableAta
+  );
+
+  try {
+    await program.methods
+      .settleJob(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        customerInfraData.account.customerInfraCount,
+        jobData.account.jobCount
+      )
+      .accounts({
+        countryState: countryAddress,
+        driverInfra: driverInfraData.publicKey,
+        customerInfra: customerInfraData.publicKey,
+        job: jobData.publicKey,
+        jobEsrowStable: jobEscrowStableAta,
+        driverInfraStable: driverInfraStableAta,
+        customerInfraStable: customerInfraStableAta,
+        countryStableAccount: countryStableAta,
+        globalState: globalAddress,
+        globalStableAccount: globalStableAta,
+        keeperStable: keeperStableAta,
+        keeper: keeper.publicKey,
+        mint: stableMint,
+      })
+      .signers([keeper])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail();
+    }
+  } catch (error) {
+    if (shouldPass) {
+      console.log("error:", error);
+      throw error;
+    } else {
+      expect(error.message).to.include("FinalizeDurationNotOver");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const data = await getJobData(
+    program,
+    customerInfraData.publicKey,
+    driverUuid
+  );
+  assert.equal(!!data, false, `Account should be closed by got ${data}`);
+  const escrowInfo = await program.provider.connection.getAccountInfo(
+    jobEscrowStableAta
+  );
+  assert.equal(escrowInfo, null, "escrow should be closed");
+  const keeperAfter = await getAccount(
+    program.provider.connection,
+    keeperStableAta
+  );
+  expect(Number(keeperAfter.amount - keeperBefore.amount)).to.equal(
+    +countryData.keeperRewardCent,
+    "keeperReward"
+  );
+};
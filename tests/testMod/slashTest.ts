@@ -0,0 +1,208 @@
+import * as anchor from "@project-serum/anchor";
+import { getAccount, getAssociatedTokenAddress } from "@solana/spl-token";
+import { assert, expect } from "chai";
+import {
+  getCountryAddress,
+  getCountryData,
+  getDriverInfraDataByUpdateAuthority,
+  getGlobalAddress,
+  getSlashRecordAddress,
+} from "../utils/pda";
+import { IAllAccounts } from "../utils/types";
+
+const getSlashAccounts = async (allAccounts: IAllAccounts) => {
+  const { program, stableMint, driverInfraOwner } = allAccounts;
+  const countryPda = getCountryAddress(program);
+  const driverInfraData = await getDriverInfraDataByUpdateAuthority(
+    program,
+    driverInfraOwner.publicKey
+  );
+  const countryStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    countryPda,
+    true
+  );
+  const driverInfraStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    driverInfraData.publicKey,
+    true
+  );
+  return { countryPda, driverInfraData, countryStableAta, driverInfraStableAta };
+};
+
+export const driverInfraSlash = async (
+  allAccounts: IAllAccounts,
+  slashMultiplierBasisPoint: number
+) => {
+  const { program, mainWallet1, stableMint } = allAccounts;
+  const { countryPda, driverInfraData, countryStableAta, driverInfraStableAta } =
+    await getSlashAccounts(allAccounts);
+  const countryBefore = await getCountryData(program);
+  const slashRecordAddress = getSlashRecordAddress(
+    program,
+    countryBefore.slashCounter
+  );
+  const driverBefore = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+
+  await program.methods
+    .driverInfraSlash(
+      "SGP",
+      driverInfraData.account.driverInfraCount,
+      new anchor.BN(slashMultiplierBasisPoint),
+      { protocolViolation: {} },
+      null,
+      null
+    )
+    .accounts({
+      countryState: countryPda,
+      countryAuthority: mainWallet1.publicKey,
+      driverInfra: driverInfraData.publicKey,
+      countryStableAccount: countryStableAta,
+      driverStableAccount: driverInfraStableAta,
+      slashRecord: slashRecordAddress,
+      mint: stableMint,
+    })
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const expectedAmount = Math.round(
+    (+countryBefore.baseSlashAmount * slashMultiplierBasisPoint) / 10_000
+  );
+  const slashRecord = await program.account.slashRecord.fetch(
+    slashRecordAddress
+  );
+  assert.equal(
+    slashRecord.infra.toString(),
+    driverInfraData.publicKey.toString(),
+    "infra"
+  );
+  assert.deepEqual(slashRecord.infraType, { driverInfra: {} }, "infraType");
+  assert.deepEqual(slashRecord.reason, { protocolViolation: {} }, "reason");
+  assert.equal(slashRecord.job, null, "job");
+  expect(+slashRecord.amount).to.equal(expectedAmount, "amount");
+  expect(+slashRecord.appealDeadline).to.equal(
+    +slashRecord.slashedTime + +countryBefore.slashAppealPeriodSec,
+    "appealDeadline"
+  );
+  const countryAfter = await getCountryData(program);
+  expect(+countryAfter.slashCounter).to.equal(
+    +countryBefore.slashCounter + 1,
+    "slashCounter"
+  );
+  const driverAfter = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  expect(Number(driverBefore.amount - driverAfter.amount)).to.equal(
+    expectedAmount,
+    "slashed"
+  );
+};
+
+export const appealDriverInfraSlash = async (
+  allAccounts: IAllAccounts,
+  slashCount: anchor.BN,
+  shouldPass = true
+) => {
+  const { program, driverInfraOwner } = allAccounts;
+  const { countryPda, driverInfraData } = await getSlashAccounts(allAccounts);
+  const slashRecordAddress = getSlashRecordAddress(program, slashCount);
+
+  try {
+    await program.methods
+      .appealDriverInfraSlash(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        slashCount
+      )
+      .accounts({
+        countryState: countryPda,
+        driverInfra: driverInfraData.publicKey,
+        driverInfraOwner: driverInfraOwner.publicKey,
+        slashRecord: slashRecordAddress,
+      })
+      .signers([driverInfraOwner])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Appeal should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("SlashAlreadyAppealed");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const slashRecord = await program.account.slashRecord.fetch(
+    slashRecordAddress
+  );
+  assert.notEqual(slashRecord.appealedTime, null, "appealedTime");
+};
+
+export const reverseDriverInfraSlash = async (
+  allAccounts: IAllAccounts,
+  slashCount: anchor.BN,
+  shouldPass = true
+) => {
+  const { program, mainWallet1, bozo, stableMint } = allAccounts;
+  const { countryPda, driverInfraData, countryStableAta, driverInfraStableAta } =
+    await getSlashAccounts(allAccounts);
+  const slashRecordAddress = getSlashRecordAddress(program, slashCount);
+  const globalAuthority = shouldPass ? mainWallet1 : bozo;
+  const driverBefore = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+
+  try {
+    await program.methods
+      .reverseDriverInfraSlash(
+        "SGP",
+        driverInfraData.account.driverInfraCount,
+        slashCount
+      )
+      .accounts({
+        globalState: getGlobalAddress(program),
+        globalAuthority: globalAuthority.publicKey,
+        countryState: countryPda,
+        driverInfra: driverInfraData.publicKey,
+        slashRecord: slashRecordAddress,
+        countryStableAccount: countryStableAta,
+        driverStableAccount: driverInfraStableAta,
+        mint: stableMint,
+      })
+      .signers([globalAuthority])
+      .rpc();
+    if (!shouldPass) {
+      assert.fail("Reverse should fail");
+    }
+  } catch (error) {
+    if (shouldPass) {
+      throw error;
+    } else {
+      expect(error.message).to.include("Error Code: ConstraintRaw");
+      return;
+    }
+  }
+
+  // ASSERT TEST
+  const slashRecord = await program.account.slashRecord.fetch(
+    slashRecordAddress
+  );
+  assert.notEqual(slashRecord.reversedTime, null, "reversedTime");
+  const driverAfter = await getAccount(
+    program.provider.connection,
+    driverInfraStableAta
+  );
+  expect(Number(driverAfter.amount - driverBefore.amount)).to.equal(
+    +slashRecord.amount,
+    "refund"
+  );
+};
@@ -1,12 +1,15 @@
+import * as anchor from "@project-serum/anchor";
 import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   TOKEN_PROGRAM_ID,
+  getAccount,
   getAssociatedTokenAddress,
 } from "@solana/spl-token";
 import {
   getGlobalAddress,
   getGlobalData,
   getVehicleAddress,
+  getVehicleData,
 } from "../utils/pda";
 import { IAllAccounts } from "../utils/types";
 import { assert, expect } from "chai";
@@ -65,3 +68,119 @@ export const addVehicle = async (
     }
   }
 };
+
+const getVehicleVerificationAccounts = async (
+  allAccounts: IAllAccounts,
+  vehicleCount: anchor.BN
+) => {
+  const { program, mainWallet1, stableMint } = allAccounts;
+  const globalPda = getGlobalAddress(program);
+  const vehiclePda = getVehicleAddress(program, vehicleCount);
+  const vehicleStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    vehiclePda,
+    true
+  );
+  const globalStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    globalPda,
+    true
+  );
+  const initializerStableAta = await getAssociatedTokenAddress(
+    stableMint,
+    mainWallet1.publicKey
+  );
+  return {
+    globalState: globalPda,
+    vehicle: vehiclePda,
+    vehicleEscrowAccount: vehicleStableAta,
+    globalTokenAccount: globalStableAta,
+    initializerTokenAccount: initializerStableAta,
+    initializer: mainWallet1.publicKey,
+    globalAuthority: mainWallet1.publicKey,
+    mint: stableMint,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  };
+};
+
+export const approveVehicle = async (
+  allAccounts: IAllAccounts,
+  vehicleCount: anchor.BN
+) => {
+  const { program, mainWallet1 } = allAccounts;
+  const accounts = await getVehicleVerificationAccounts(
+    allAccounts,
+    vehicleCount
+  );
+  const escrowBefore = await getAccount(
+    program.provider.connection,
+    accounts.vehicleEscrowAccount
+  );
+  const globalBefore = await getAccount(
+    program.provider.connection,
+    accounts.globalTokenAccount
+  );
+
+  await program.methods
+    .approveVehicle(vehicleCount)
+    .accounts(accounts)
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const vehicleData = await getVehicleData(program, vehicleCount);
+  assert.equal(vehicleData.isValid, true, "isValid");
+  const globalAfter = await getAccount(
+    program.provider.connection,
+    accounts.globalTokenAccount
+  );
+  expect(Number(globalAfter.amount - globalBefore.amount)).to.equal(
+    Number(escrowBefore.amount),
+    "global treasury"
+  );
+  const escrowAfter = await program.provider.connection.getAccountInfo(
+    accounts.vehicleEscrowAccount
+  );
+  assert.equal(escrowAfter, null, "escrow closed");
+};
+
+export const rejectVehicle = async (
+  allAccounts: IAllAccounts,
+  vehicleCount: anchor.BN
+) => {
+  const { program, mainWallet1 } = allAccounts;
+  const accounts = await getVehicleVerificationAccounts(
+    allAccounts,
+    vehicleCount
+  );
+  const escrowBefore = await getAccount(
+    program.provider.connection,
+    accounts.vehicleEscrowAccount
+  );
+  const initializerBefore = await getAccount(
+    program.provider.connection,
+    accounts.initializerTokenAccount
+  );
+
+  await program.methods
+    .rejectVehicle(vehicleCount)
+    .accounts(accounts)
+    .signers([mainWallet1])
+    .rpc();
+
+  // ASSERT TEST
+  const vehicleData = await getVehicleData(program, vehicleCount);
+  assert.equal(vehicleData.isValid, false, "isValid");
+  assert.equal(vehicleData.isRejected, true, "isRejected");
+  const initializerAfter = await getAccount(
+    program.provider.connection,
+    accounts.initializerTokenAccount
+  );
+  expect(
+    Number(initializerAfter.amount - initializerBefore.amount)
+  ).to.equal(Number(escrowBefore.amount), "refund");
+  const escrowAfter = await program.provider.connection.getAccountInfo(
+    accounts.vehicleEscrowAccount
+  );
+  assert.equal(escrowAfter, null, "escrow closed");
+};
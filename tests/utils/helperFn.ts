@@ -15,7 +15,7 @@ import {
   Transaction,
 } from "@solana/web3.js";
 import { RideNetwork } from "../../target/types/ride_network";
-import { IAllAccounts } from "./types";
+import { DriverQuote, IAllAccounts } from "./types";
 
 export const signAndSendTx = async (
   connection: Connection,
@@ -157,3 +157,65 @@ export const delayScript = (ms: number): Promise<void> => {
     }, ms);
   });
 };
+
+// Mirrors the on-chain rate card, partial km and minutes round up
+export const computeFare = (
+  country: {
+    baseRateCent: anchor.BN;
+    minKmRateCent: anchor.BN;
+    minMinFeeCent: anchor.BN;
+  },
+  distanceM: anchor.BN,
+  durationSec: anchor.BN
+): anchor.BN => {
+  const roundUpDiv = (value: anchor.BN, divisor: number) =>
+    value.addn(divisor - 1).divn(divisor);
+  return country.baseRateCent
+    .add(roundUpDiv(distanceM.mul(country.minKmRateCent), 1_000))
+    .add(roundUpDiv(durationSec.mul(country.minMinFeeCent), 60));
+};
+
+// Mirrors the on-chain quote message: domain prefix and borsh encoded quote
+export const driverQuoteMessage = (quote: DriverQuote): Buffer => {
+  const uuid = Buffer.from(quote.driverUuid);
+  const uuidLen = Buffer.alloc(4);
+  uuidLen.writeUInt32LE(uuid.length);
+  return Buffer.concat([
+    Buffer.from("ride_network:driver_quote:v1"),
+    quote.driverInfra.toBuffer(),
+    uuidLen,
+    uuid,
+    quote.service.toArrayLike(Buffer, "le", 8),
+    quote.fareCent.toArrayLike(Buffer, "le", 8),
+    quote.expiry.toArrayLike(Buffer, "le", 8),
+    quote.nonce.toArrayLike(Buffer, "le", 8),
+  ]);
+};
+
+// Events emitted by the program in a confirmed transaction
+export const getEvents = async (
+  program: Program<RideNetwork>,
+  signature: string,
+  name: string
+) => {
+  const connection = program.provider.connection;
+  const latestBlockHash = await connection.getLatestBlockhash();
+  await connection.confirmTransaction(
+    {
+      blockhash: latestBlockHash.blockhash,
+      lastValidBlockHeight: latestBlockHash.lastValidBlockHeight,
+      signature,
+    },
+    "confirmed"
+  );
+  const tx = await connection.getTransaction(signature, {
+    commitment: "confirmed",
+  });
+  const parser = new anchor.EventParser(
+    program.programId,
+    new anchor.BorshCoder(program.idl)
+  );
+  return [...parser.parseLogs(tx.meta.logMessages)]
+    .filter((event) => event.name === name)
+    .map((event) => event.data);
+};
@@ -378,3 +378,49 @@ export const getJobDataByAddress = async (
   const data = await program.account.job.fetch(jobAddress);
   return data;
 };
+
+export const getDisputeAddress = (
+  program: Program<RideNetwork>,
+  jobAddress: PublicKey
+) => {
+  const [disputeAddress, _disputeAddressBump] =
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute"), jobAddress.toBuffer()],
+      program.programId
+    );
+  return disputeAddress;
+};
+
+export const getSlashRecordAddress = (
+  program: Program<RideNetwork>,
+  slashCount: anchor.BN,
+  alpha3Code = "SGP"
+) => {
+  const [slashRecordAddress, _slashRecordAddressBump] =
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("slash_record"),
+        getCountryAddress(program, alpha3Code).toBuffer(),
+        slashCount.toBuffer("le", 8),
+      ],
+      program.programId
+    );
+  return slashRecordAddress;
+};
+
+export const getDriverProfileAddress = (
+  program: Program<RideNetwork>,
+  driverInfraAddress: PublicKey,
+  driverUuid: string
+) => {
+  const [driverProfileAddress, _driverProfileAddressBump] =
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("driver_profile"),
+        driverInfraAddress.toBuffer(),
+        Buffer.from(driverUuid),
+      ],
+      program.programId
+    );
+  return driverProfileAddress;
+};
@@ -15,9 +15,6 @@ export interface IInitOrUpdateCountryAssert {
   platformFeeBasisPoint: number;
   waitingFeeSec: anchor.BN;
   waitingFeeCent: anchor.BN;
-  driverCancellationFeeSec: anchor.BN;
-  customerCancellationFeeSec: anchor.BN;
-  cancellationFeeCent: anchor.BN;
   baseRateCent: anchor.BN;
   minKmRateCent: anchor.BN;
   minMinFeeCent: anchor.BN;
@@ -26,6 +23,30 @@ export interface IInitOrUpdateCountryAssert {
   minCustomerInfraDeposit: anchor.BN;
   disputeWaitoutPeriod: anchor.BN;
   baseSlashAmount: anchor.BN;
+  keeperRewardCent: anchor.BN;
+  infraUnbondingPeriodSec: anchor.BN;
+  depositGracePeriodSec: anchor.BN;
+  slashAppealPeriodSec: anchor.BN;
+  minReputationBasisPoint: number;
+  reputationMinMatchedRide: anchor.BN;
+  depositTiers: {
+    belowReputationBasisPoint: number;
+    depositMultiplierBasisPoint: number;
+  }[];
+  maxReservationHoldSec: anchor.BN;
+  driverHeartbeatTimeoutSec: anchor.BN;
+  maxWaitingFeeCent: anchor.BN;
+  noShowWaitSec: anchor.BN;
+  noShowFeeCent: anchor.BN;
+  cancellationTiers: CancellationTier[];
+  pickupSpeedMPerSec: anchor.BN;
+}
+
+export interface CancellationTier {
+  stage: { afterAcceptance: {} } | { afterArrival: {} } | { etaBreach: {} };
+  cancelledBy: { driverInfra: {} } | { customerInfra: {} };
+  afterSec: anchor.BN;
+  charge: { flatCent: { 0: anchor.BN } } | { fareBasisPoint: { 0: number } };
 }
 
 export interface IInitDriverInfraAssert {
@@ -76,3 +97,35 @@ export interface EncryptedData {
   encryptedData: string;
   encryptedCombinedRandBase64: string;
 }
+
+export interface RideRequirements {
+  service: anchor.BN;
+  passengerTypes: anchor.BN[];
+  passengerCount: number;
+}
+
+export interface TripEstimate {
+  distanceM: anchor.BN;
+  durationSec: anchor.BN;
+}
+
+export interface DriverQuote {
+  driverInfra: anchor.web3.PublicKey;
+  driverUuid: string;
+  service: anchor.BN;
+  fareCent: anchor.BN;
+  expiry: anchor.BN;
+  nonce: anchor.BN;
+}
+
+export interface QuoteOverride {
+  signer?: anchor.web3.Keypair;
+  expiry?: anchor.BN;
+  nonce?: anchor.BN;
+}
+
+export type DriverAvailability =
+  | { online: {} }
+  | { busy: {} }
+  | { onBreak: {} }
+  | { offline: {} };